@@ -0,0 +1,162 @@
+//! Decomposition of IEEE 754 decimal floating-point numbers
+//! in the binary integer decimal (BID) encoding.
+
+/// A `decimal64` value, held as its BID-encoded bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal64(pub u64);
+
+/// A `decimal128` value, held as its BID-encoded bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal128(pub u128);
+
+/// Get the "parts" of an IEEE decimal floating-point number,
+/// analogous to [crate::ToFloatParts].
+pub trait ToDecimalParts: Copy {
+    /// Maximum number of decimal digits in the coefficient.
+    /// For example, 16 for `decimal64`.
+    const NUM_COEFF_DIGITS: u32;
+
+    /// Number of binary digits for the biased exponent. For
+    /// example, 10 for `decimal64`.
+    const NUM_EXP_BITS: u32;
+
+    /// Bias of the stored exponent.
+    const EXP_BIAS: Self::Exp;
+
+    /// Largest representable value exponent.
+    const EXP_MAX: Self::Exp;
+
+    /// Smallest representable value exponent.
+    const EXP_MIN: Self::Exp;
+
+    /// Exponent denoting an infinity.
+    const EXP_INF: Self::Exp;
+
+    /// Exponent denoting a NaN. Unlike binary formats, a
+    /// decimal NaN may have a zero payload, so it cannot share
+    /// an exponent with infinity.
+    const EXP_NAN: Self::Exp;
+
+    /// Type for integer representation of the coefficient.
+    type Coeff;
+    /// Type for integer representation of the decimal
+    /// exponent.
+    type Exp;
+
+    /// Given a decimal float, return the coefficient, decimal
+    /// exponent and sign, such that the value is `sign *
+    /// coeff * 10^exp`.
+    ///
+    /// The sign will be +1 for positive or -1 for negative.
+    ///
+    /// Non-canonical coefficients (larger than the format
+    /// allows) are decoded as zero, as the standard requires.
+    /// Infinities have exponent [Self::EXP_INF] and a zero
+    /// coefficient. NaNs have exponent [Self::EXP_NAN] and
+    /// their payload as the coefficient.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{ToDecimalParts, Decimal128};
+    /// let one = Decimal128(0x3040_0000_0000_0000_0000_0000_0000_0001);
+    /// assert_eq!(one.to_decimal_parts(), (1, 0, 1));
+    /// let x = Decimal128(0xb03c_0000_0000_0000_0000_0000_0000_02ee);
+    /// assert_eq!(x.to_decimal_parts(), (750, -2, -1));
+    /// ```
+    fn to_decimal_parts(self) -> (Self::Coeff, Self::Exp, i8);
+}
+
+macro_rules! to_decimal_parts {
+    ($c:ty) => {
+        fn to_decimal_parts(self) -> (Self::Coeff, Self::Exp, i8) {
+            type C = $c;
+
+            let wc = C::BITS;
+            let ne = Self::NUM_EXP_BITS;
+            let bits = self.0;
+
+            let sign = 1 - ((bits >> (wc - 2)) & 2) as i8;
+            let comb = bits >> (wc - 6);
+
+            if comb & 0b11111 == 0b11110 {
+                return (0, Self::EXP_INF, sign);
+            }
+            if comb & 0b11111 == 0b11111 {
+                let payload = bits & ((1 << (wc - ne - 4)) - 1);
+                return (payload, Self::EXP_NAN, sign);
+            }
+
+            // With the large-coefficient form, the exponent
+            // field starts two bits lower and the coefficient
+            // gets an implicit leading `100`.
+            let (exp, coeff) = if comb & 0b11000 == 0b11000 {
+                let nc = wc - 3 - ne;
+                let coeff = (0b100 << nc) | (bits & ((1 << nc) - 1));
+                ((bits >> nc) & ((1 << ne) - 1), coeff)
+            } else {
+                let nc = wc - 1 - ne;
+                ((bits >> nc) & ((1 << ne) - 1), bits & ((1 << nc) - 1))
+            };
+
+            let coeff = if coeff < C::pow(10, Self::NUM_COEFF_DIGITS) {
+                coeff
+            } else {
+                0
+            };
+            (coeff, exp as i16 - Self::EXP_BIAS, sign)
+        }
+    };
+}
+
+impl ToDecimalParts for Decimal64 {
+    const NUM_COEFF_DIGITS: u32 = 16;
+    const NUM_EXP_BITS: u32 = 10;
+
+    const EXP_BIAS: i16 = 398;
+    const EXP_MAX: i16 = 369;
+    const EXP_MIN: i16 = -Self::EXP_BIAS;
+    const EXP_INF: i16 = Self::EXP_MAX + 1;
+    const EXP_NAN: i16 = Self::EXP_MAX + 2;
+
+    type Coeff = u64;
+    type Exp = i16;
+
+    to_decimal_parts!{u64}
+}
+
+impl ToDecimalParts for Decimal128 {
+    const NUM_COEFF_DIGITS: u32 = 34;
+    const NUM_EXP_BITS: u32 = 14;
+
+    const EXP_BIAS: i16 = 6176;
+    const EXP_MAX: i16 = 6111;
+    const EXP_MIN: i16 = -Self::EXP_BIAS;
+    const EXP_INF: i16 = Self::EXP_MAX + 1;
+    const EXP_NAN: i16 = Self::EXP_MAX + 2;
+
+    type Coeff = u128;
+    type Exp = i16;
+
+    to_decimal_parts!{u128}
+}
+
+#[test]
+fn test_to_decimal_parts_decimal64() {
+    assert_eq!(Decimal64(0x31c0_0000_0000_0001).to_decimal_parts(), (1, 0, 1));
+    assert_eq!(Decimal64(0xb180_0000_0000_02ee).to_decimal_parts(), (750, -2, -1));
+    let max_coeff = Decimal64(0x6c73_86f2_6fc0_ffff);
+    assert_eq!(max_coeff.to_decimal_parts(), (9_999_999_999_999_999, 0, 1));
+    assert_eq!(Decimal64(0x7800_0000_0000_0000).to_decimal_parts(), (0, Decimal64::EXP_INF, 1));
+    assert_eq!(Decimal64(0xfc00_0000_0000_002a).to_decimal_parts(), (42, Decimal64::EXP_NAN, -1));
+}
+
+#[test]
+fn test_to_decimal_parts_decimal128() {
+    assert_eq!(
+        Decimal128(0xf800_0000_0000_0000_0000_0000_0000_0000).to_decimal_parts(),
+        (0, Decimal128::EXP_INF, -1),
+    );
+    // The large-coefficient form is always non-canonical here.
+    let noncanonical = Decimal128(0x6c10_0000_0000_0000_0000_0000_0000_0005);
+    assert_eq!(noncanonical.to_decimal_parts(), (0, 0, 1));
+}
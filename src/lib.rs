@@ -11,6 +11,189 @@ macro_rules! mask {
     };
 }
 
+#[cfg(feature = "decimal")]
+mod decimal;
+#[cfg(feature = "decimal")]
+pub use decimal::*;
+
+/// The parts of a float, as returned by
+/// [ToFloatParts::to_float_parts], gathered into a struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FloatParts<S, E> {
+    /// Significand bits.
+    pub sigbits: S,
+    /// Exponent relative to the binary point.
+    pub exp: E,
+    /// Sign: +1 for positive or -1 for negative.
+    pub sign: i8,
+}
+
+impl<S: Copy + Into<u128>, E: Copy + Into<i32>> FloatParts<S, E> {
+    /// Check that these parts are a valid decomposition for
+    /// the float type `T`, as [ToFloatParts::to_float_parts]
+    /// would produce: the sign is ±1, the sigbits fit in the
+    /// significand, the exponent is in range, and the implicit
+    /// 1 bit is set exactly for normal exponents. Subnormal
+    /// sigbits must also be even, since their low bit is not
+    /// stored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{FloatParts, FloatPartsError};
+    /// let one = FloatParts { sigbits: 1u32 << 23, exp: -23i16, sign: 1 };
+    /// assert_eq!(one.validate::<f32>(), Ok(()));
+    /// let bad = FloatParts { sign: 0, ..one };
+    /// assert_eq!(bad.validate::<f32>(), Err(FloatPartsError::BadSign));
+    /// let bad = FloatParts { sigbits: 1, ..one };
+    /// assert_eq!(bad.validate::<f32>(), Err(FloatPartsError::ImplicitBit));
+    /// ```
+    pub fn validate<T>(&self) -> Result<(), FloatPartsError>
+    where
+        T: ToFloatParts<SigBits = S, Exp = E>,
+    {
+        let ns = T::NUM_SIG_BITS;
+        let sigbits: u128 = self.sigbits.into();
+        let exp: i32 = self.exp.into();
+        let (exp_min, exp_inf_nan) = (T::EXP_MIN.into(), T::EXP_INF_NAN.into());
+
+        if self.sign != 1 && self.sign != -1 {
+            return Err(FloatPartsError::BadSign);
+        }
+        if sigbits >> ns != 0 {
+            return Err(FloatPartsError::SigBitsTooWide);
+        }
+        if !(exp_min..=exp_inf_nan).contains(&exp) {
+            return Err(FloatPartsError::ExpOutOfRange);
+        }
+        let implicit = sigbits >> (ns - 1) != 0;
+        if exp == exp_min {
+            if sigbits & 1 != 0 {
+                return Err(FloatPartsError::OddSubnormal);
+            }
+        } else if implicit != (exp != exp_inf_nan) {
+            return Err(FloatPartsError::ImplicitBit);
+        }
+        Ok(())
+    }
+}
+
+/// [ToFloatParts::exponent_bucket] sentinel for zeros.
+pub const EXP_BUCKET_ZERO: i32 = i32::MIN;
+/// [ToFloatParts::exponent_bucket] sentinel for subnormals.
+pub const EXP_BUCKET_SUBNORMAL: i32 = i32::MIN + 1;
+/// [ToFloatParts::exponent_bucket] sentinel for infinities.
+pub const EXP_BUCKET_INF: i32 = i32::MAX - 1;
+/// [ToFloatParts::exponent_bucket] sentinel for NaNs.
+pub const EXP_BUCKET_NAN: i32 = i32::MAX;
+
+/// Description of a binary IEEE-style floating-point format,
+/// for [nearest_in_format]. The fields mirror
+/// [ToFloatParts::NUM_SIG_BITS] and
+/// [ToFloatParts::NUM_EXP_BITS].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FloatFormat {
+    /// Significant bits, including the implicit 1.
+    pub num_sig_bits: u32,
+    /// Exponent field bits.
+    pub num_exp_bits: u32,
+    /// Whether the all-ones exponent is reserved for
+    /// infinities and NaNs, as in IEEE formats. If not, as in
+    /// the fp8 E4M3 format, it holds finite values, all-ones
+    /// significand bits alone are NaN, and there are no
+    /// infinities.
+    pub has_infinity: bool,
+}
+
+impl FloatFormat {
+    /// bfloat16.
+    pub const BFLOAT16: FloatFormat = FloatFormat { num_sig_bits: 8, num_exp_bits: 8, has_infinity: true };
+    /// NVIDIA TensorFloat-32.
+    pub const TF32: FloatFormat = FloatFormat { num_sig_bits: 11, num_exp_bits: 8, has_infinity: true };
+    /// OCP fp8 E5M2.
+    pub const FP8_E5M2: FloatFormat = FloatFormat { num_sig_bits: 3, num_exp_bits: 5, has_infinity: true };
+    /// OCP fp8 E4M3, which has no infinities.
+    pub const FP8_E4M3: FloatFormat = FloatFormat { num_sig_bits: 4, num_exp_bits: 4, has_infinity: false };
+}
+
+/// Rounding direction for [ToFloatParts::round_as].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even: the IEEE default.
+    NearestEven,
+    /// Round toward zero: truncate.
+    TowardZero,
+    /// Round toward positive infinity.
+    TowardPositive,
+    /// Round toward negative infinity.
+    TowardNegative,
+}
+
+/// Error returned by [ToFloatParts::try_cmp] when an
+/// operand is a NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanComparison;
+
+impl std::fmt::Display for NanComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "comparison with NaN")
+    }
+}
+
+impl std::error::Error for NanComparison {}
+
+/// Error returned by [ToFloatParts::from_hex_float_str].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input is not a hex float, infinity or NaN.
+    Invalid,
+    /// The significand has no digits.
+    NoDigits,
+    /// The binary exponent is empty or malformed.
+    BadExponent,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::Invalid => write!(f, "invalid hex float"),
+            ParseError::NoDigits => write!(f, "hex float has no significand digits"),
+            ParseError::BadExponent => write!(f, "invalid hex float exponent"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error returned by [FloatParts::validate].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatPartsError {
+    /// The sign is not +1 or -1.
+    BadSign,
+    /// The sigbits are wider than the significand.
+    SigBitsTooWide,
+    /// The exponent is outside `[EXP_MIN, EXP_INF_NAN]`.
+    ExpOutOfRange,
+    /// The implicit 1 bit is missing from a normal, or present
+    /// in an infinity or NaN.
+    ImplicitBit,
+    /// Subnormal sigbits have their unstored low bit set.
+    OddSubnormal,
+}
+
+impl std::fmt::Display for FloatPartsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FloatPartsError::BadSign => write!(f, "float parts sign is not ±1"),
+            FloatPartsError::SigBitsTooWide => write!(f, "float parts sigbits too wide"),
+            FloatPartsError::ExpOutOfRange => write!(f, "float parts exponent out of range"),
+            FloatPartsError::ImplicitBit => write!(f, "float parts implicit bit mismatch"),
+            FloatPartsError::OddSubnormal => write!(f, "float parts subnormal sigbits odd"),
+        }
+    }
+}
+
+impl std::error::Error for FloatPartsError {}
+
 /// Get the "parts" of an IEEE floating-point number.
 pub trait ToFloatParts : Copy {
     /// Number of significant binary digits for the type,
@@ -18,6 +201,11 @@ pub trait ToFloatParts : Copy {
     /// `f32`.
     const NUM_SIG_BITS: u32;
 
+    /// Bit index of the implicit 1 in the sigbits returned by
+    /// [ToFloatParts::to_float_parts] for normal values: the
+    /// top bit of the significand. For example, 23 for `f32`.
+    const IMPLICIT_BIT_POS: u32 = Self::NUM_SIG_BITS - 1;
+
     /// Number of binary digits for the exponent type. For
     /// example, 8 for `f32`.
     const NUM_EXP_BITS: u32;
@@ -25,16 +213,38 @@ pub trait ToFloatParts : Copy {
     /// Adjusted exponent denoting Inf and NaN values.
     const EXP_INF_NAN: Self::Exp;
 
-    /// Smallest representable value adjusted exponent, indicating denorm.
+    /// Largest representable value adjusted exponent.
     const EXP_MAX: Self::Exp;
 
     /// Smallest representable value adjusted exponent, indicating denorm.
     const EXP_MIN: Self::Exp;
 
+    /// The parts of the largest finite value, as returned by
+    /// [ToFloatParts::to_float_parts]: all-ones sigbits with
+    /// exponent [Self::EXP_MAX].
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::MAX_PARTS, f32::MAX.to_float_parts());
+    /// ```
+    const MAX_PARTS: (Self::SigBits, Self::Exp, i8);
+
+    /// Machine epsilon, `2^-(NUM_SIG_BITS - 1)`: the distance
+    /// from 1.0 to the next larger float. Equal to the `std`
+    /// `EPSILON` of the type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(<f32 as ToFloatParts>::EPSILON, f32::EPSILON);
+    /// ```
+    const EPSILON: Self;
+
     /// Amount of adjustment applied to exponent internally
     /// for representable values.
     const EXP_ADJUST: i16 =
-        mask!(Self::NUM_EXP_BITS - 1) as i16 - Self::NUM_SIG_BITS as i16 + 1;
+        mask!(Self::NUM_EXP_BITS - 1) as i16 + Self::NUM_SIG_BITS as i16 - 1;
 
     /// Type for integer representation of the
     /// mantissa/significand. This should be an unsigned
@@ -69,84 +279,4151 @@ pub trait ToFloatParts : Copy {
     /// # Examples
     /// ```
     /// # use float_parts::ToFloatParts;
-    /// assert_eq!((1.0f32).to_float_parts(), (1 << 24, 23, 1));
+    /// assert_eq!((1.0f32).to_float_parts(), (1 << 23, -23, 1));
     /// let denorm = -f32::powf(2.0, -129.0);
     /// assert_eq!(denorm.to_float_parts(), (1 << 21, f32::EXP_MIN, -1));
     /// assert_eq!(f32::NAN.to_float_parts().1, f32::EXP_INF_NAN);
     /// ```
     fn to_float_parts(self) -> (Self::SigBits, Self::Exp, i8);
-}
 
-macro_rules! to_float_parts {
-    ($s:ty, $e:ty) => {
-        fn to_float_parts(self) -> (Self::SigBits, Self::Exp, i8) {
-            type S = $s;
-            type E = $e;
+    /// Given sigbits, exponent and sign in the form returned
+    /// by [ToFloatParts::to_float_parts], reassemble the
+    /// float.
+    ///
+    /// The parts are assumed to be valid: bits of `sigbits`
+    /// outside the stored significand field are ignored, and
+    /// an exponent outside `[Self::EXP_MIN, Self::EXP_INF_NAN]`
+    /// gives an unspecified result.
+    ///
+    /// With exponent [Self::EXP_INF_NAN], zero sigbits give an
+    /// infinity, while nonzero sigbits are taken as a NaN
+    /// payload, quiet bit included, so that NaNs round-trip
+    /// bit-exactly: signaling NaNs stay signaling.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// for x in [1.0f32, -0.0, f32::MAX, -f32::powf(2.0, -129.0), f32::INFINITY] {
+    ///     let (sigbits, exp, sign) = x.to_float_parts();
+    ///     assert_eq!(f32::from_float_parts(sigbits, exp, sign).to_bits(), x.to_bits());
+    /// }
+    /// let snan = f32::from_bits(0xff80_0001);
+    /// let (sigbits, exp, sign) = snan.to_float_parts();
+    /// assert_eq!(f32::from_float_parts(sigbits, exp, sign).to_bits(), 0xff80_0001);
+    /// ```
+    fn from_float_parts(sigbits: Self::SigBits, exp: Self::Exp, sign: i8) -> Self;
 
-            let ws = 8 * std::mem::size_of::<S>();
-            let ns = Self::NUM_SIG_BITS;
-            let ne = Self::NUM_EXP_BITS;
+    /// Like [ToFloatParts::from_float_parts], but with the
+    /// exponent clamped to the representable range rather
+    /// than producing garbage.
+    ///
+    /// Zero sigbits give a zero of the given sign, whatever
+    /// the exponent. Otherwise, an exponent above
+    /// [Self::EXP_MAX] gives an infinity of the given sign. An
+    /// exponent below [Self::EXP_MIN] has the sigbits shifted
+    /// right to compensate, truncating toward zero, producing a
+    /// subnormal or zero. This function never produces a NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_float_parts_saturating(1 << 23, 1000, -1), f32::NEG_INFINITY);
+    /// let tiny = f32::from_float_parts_saturating(1 << 23, f32::EXP_MIN - 2, 1);
+    /// assert_eq!(tiny, f32::powf(2.0, -129.0));
+    /// assert_eq!(f32::from_float_parts_saturating(1 << 23, -1000, 1), 0.0);
+    /// assert_eq!(f32::from_float_parts_saturating(0, 1000, 1).to_bits(), 0);
+    /// ```
+    fn from_float_parts_saturating(sigbits: Self::SigBits, exp: Self::Exp, sign: i8) -> Self;
 
-            let bits = self.to_bits();
+    /// Build the float nearest to `sign * sig * 2^exp`, where
+    /// `sig` is an arbitrary (not necessarily normalized)
+    /// integer significand.
+    ///
+    /// The significand is shifted so that its leading 1 lands
+    /// at the implicit-bit position, with `exp` adjusted to
+    /// match. Dropped bits are rounded to nearest, ties to
+    /// even. Values too large for the format become an
+    /// infinity; values too small become a subnormal or a
+    /// signed zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_normalized_significand(3, -1, 1), 1.5);
+    /// assert_eq!(f32::from_normalized_significand(3 << 80, -81, -1), -1.5);
+    /// // 2^24 + 1 is a tie, rounding down to even.
+    /// assert_eq!(f32::from_normalized_significand((1 << 24) + 1, 0, 1), 16777216.0);
+    /// assert_eq!(f32::from_normalized_significand(1, 128, 1), f32::INFINITY);
+    /// assert_eq!(f32::from_normalized_significand(1, -149, 1), f32::from_bits(1));
+    /// ```
+    fn from_normalized_significand(sig: u128, exp: i32, sign: i8) -> Self;
 
-            let sign = 1 - ((bits >> (ws - 2)) & 2) as i8;
-            let exp = (bits >> (ns - 1)) & mask!(ne);
-            let mut sigbits = bits & mask!(ns - 1);
+    /// Render the float as a LaTeX math expression of the
+    /// form `(-1)^{s} \times 1.m_2 \times 2^{e}`, with the
+    /// significand fraction written in binary (trailing
+    /// zeros trimmed) and `e` the unbiased exponent.
+    ///
+    /// Subnormals (and zero) are written with a leading
+    /// `0.m` and the minimum normal exponent. Infinities
+    /// render as `\infty` or `-\infty`, NaNs as
+    /// `\mathrm{NaN}`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.5f32.to_latex(), r"(-1)^{0} \times 1.1_2 \times 2^{0}");
+    /// let denorm = -f32::powf(2.0, -128.0);
+    /// assert_eq!(denorm.to_latex(), r"(-1)^{1} \times 0.01_2 \times 2^{-126}");
+    /// assert_eq!(f32::NEG_INFINITY.to_latex(), r"-\infty");
+    /// ```
+    fn to_latex(self) -> String;
 
-            if exp == mask!(ne) {
-                return (sigbits, Self::EXP_INF_NAN, sign);
-            }
-            let is_denorm = exp == 0;
-            let exp = exp as E - Self::EXP_ADJUST;
+    /// Number of representable values one must step through
+    /// to get from `self` to `other`, or `None` if either is
+    /// a NaN.
+    ///
+    /// Signed zeros are treated as the same point: the
+    /// distance between `0.0` and `-0.0` is zero, and the
+    /// smallest subnormal of either sign is one step from
+    /// either zero. The largest finite value is one step
+    /// from the infinity of the same sign.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let x = 1.0f32;
+    /// let y = f32::from_bits(x.to_bits() + 3);
+    /// assert_eq!(x.ulps_between(y), Some(3));
+    /// assert_eq!((-0.0f32).ulps_between(0.0), Some(0));
+    /// assert_eq!(x.ulps_between(f32::NAN), None);
+    /// ```
+    fn ulps_between(self, other: Self) -> Option<u64>;
 
-            sigbits <<= is_denorm as u32;
-            sigbits |= (!is_denorm as S) << ns;
+    /// True iff `self` and `other` are exactly one
+    /// representable value apart, according to
+    /// [ToFloatParts::ulps_between]. Since signed zeros are
+    /// treated as the same point, neither zero is adjacent to
+    /// the other, but both are adjacent to the smallest
+    /// subnormals of either sign. Always false for NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let x = 1.0f32;
+    /// assert!(x.is_adjacent(f32::from_bits(x.to_bits() + 1)));
+    /// assert!(x.is_adjacent(f32::from_bits(x.to_bits() - 1)));
+    /// assert!(!x.is_adjacent(f32::from_bits(x.to_bits() + 2)));
+    /// assert!(!x.is_adjacent(x));
+    /// assert!((-0.0f32).is_adjacent(f32::from_bits(1)));
+    /// ```
+    fn is_adjacent(self, other: Self) -> bool;
 
-            (sigbits, exp, sign)
-        }
-    };
-}
+    /// Number of set bits in the decomposed significand, as
+    /// returned by [ToFloatParts::to_float_parts]. For
+    /// normals this includes the implicit 1 bit. For
+    /// infinities and NaNs this counts the payload bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.significand_popcount(), 1);
+    /// assert_eq!(1.5f32.significand_popcount(), 2);
+    /// assert_eq!(0.0f32.significand_popcount(), 0);
+    /// ```
+    fn significand_popcount(self) -> u32;
 
+    /// Convert to a Q-format fixed-point integer with
+    /// `frac_bits` fractional bits: that is, the integer
+    /// nearest to `self * 2^frac_bits`, rounding ties to
+    /// even. The conversion is exact apart from that
+    /// rounding.
+    ///
+    /// Returns `None` if the result does not fit in an
+    /// `i64`, or if `self` is infinite or NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.5f32.to_fixed_point(8), Some(384));
+    /// assert_eq!((-1.5f32).to_fixed_point(0), Some(-2));
+    /// assert_eq!(2.5f32.to_fixed_point(0), Some(2));
+    /// assert_eq!(1.0e30f32.to_fixed_point(0), None);
+    /// assert_eq!(f32::NAN.to_fixed_point(8), None);
+    /// ```
+    fn to_fixed_point(self, frac_bits: u32) -> Option<i64>;
 
-impl ToFloatParts for f32 {
-    const NUM_SIG_BITS: u32 = f32::MANTISSA_DIGITS;
-    // Why is there no constant for this in `std`?
-    const NUM_EXP_BITS: u32 = 8;
+    /// Build the float nearest to the Q-format fixed-point
+    /// value `value / 2^frac_bits`, rounding ties to even.
+    /// Results too small for the format become a subnormal
+    /// or zero; results too large become an infinity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_fixed_point(384, 8), 1.5);
+    /// assert_eq!(f32::from_fixed_point(-3, 2), -0.75);
+    /// assert_eq!(f32::from_fixed_point(1, 149), f32::from_bits(1));
+    /// ```
+    fn from_fixed_point(value: i64, frac_bits: u32) -> Self;
 
-    const EXP_INF_NAN: i16 = Self::EXP_MAX + 1;
-    const EXP_ADJUST: i16 =
-        mask!(Self::NUM_EXP_BITS - 1) as i16 - Self::NUM_SIG_BITS as i16 + 1;
-    const EXP_MAX: i16 = mask!(Self::NUM_EXP_BITS) - 1 - Self::EXP_ADJUST ;
-    const EXP_MIN: i16 = -Self::EXP_ADJUST;
+    /// Split a float into a significand float in `[1,2)`
+    /// carrying the sign, and a power-of-two scale, such that
+    /// their product is exactly `self`. Subnormals are
+    /// normalized, so that their significand is still in
+    /// `[1,2)` with a correspondingly smaller scale.
+    ///
+    /// Zeros, infinities and NaNs are returned unchanged with
+    /// a scale of `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!((-6.0f32).split_scale(), (-1.5, 4.0));
+    /// let denorm = 3.0 * f32::powf(2.0, -140.0);
+    /// let (m, s) = denorm.split_scale();
+    /// assert_eq!((m, s), (1.5, f32::powf(2.0, -139.0)));
+    /// assert_eq!(m * s, denorm);
+    /// ```
+    fn split_scale(self) -> (Self, Self);
 
-    type SigBits = u32;
-    type Exp = i16;
+    /// Compare two floats, failing with [NanComparison] if
+    /// either is a NaN rather than imposing an order on
+    /// NaNs. Signed zeros compare equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{ToFloatParts, NanComparison};
+    /// use std::cmp::Ordering;
+    /// assert_eq!(1.0f32.try_cmp(2.0), Ok(Ordering::Less));
+    /// assert_eq!((-0.0f32).try_cmp(0.0), Ok(Ordering::Equal));
+    /// assert_eq!(1.0f32.try_cmp(f32::NAN), Err(NanComparison));
+    /// ```
+    fn try_cmp(self, other: Self) -> Result<std::cmp::Ordering, NanComparison>;
 
-    to_float_parts!{u32, i16}
-}
+    /// The correctly-rounded midpoint `(self + other) / 2`,
+    /// computed in the parts domain so that there is no
+    /// intermediate overflow or double rounding.
+    ///
+    /// The midpoint of two zeros is negative only if both
+    /// are. Infinities and NaNs behave as for
+    /// `(self + other) / 2`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::MAX.midpoint_parts(f32::MAX), f32::MAX);
+    /// assert_eq!(1.0f32.midpoint_parts(2.0), 1.5);
+    /// assert_eq!((-3.0f32).midpoint_parts(1.0), -1.0);
+    /// assert_eq!(f32::from_bits(1).midpoint_parts(0.0), 0.0);
+    /// ```
+    fn midpoint_parts(self, other: Self) -> Self;
 
-impl ToFloatParts for f64 {
-    const NUM_SIG_BITS: u32 = f64::MANTISSA_DIGITS;
-    // Why is there no constant for this in `std`?
-    const NUM_EXP_BITS: u32 = 11;
+    /// Build the float nearest to `sign * fraction * 2^exp`,
+    /// where `fraction` is a real-valued significand normally
+    /// in `[1,2)`. The significand bits of `fraction` beyond
+    /// the precision of `Self` are rounded to nearest, ties
+    /// to even, in a single rounding together with any
+    /// overflow or underflow.
+    ///
+    /// The sign of `fraction` itself is ignored. An infinite
+    /// or NaN `fraction` gives an infinity or NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_sign_exp_fraction(1, 1, 1.5), 3.0);
+    /// assert_eq!(f32::from_sign_exp_fraction(-1, -2, 1.25), -0.3125);
+    /// assert_eq!(f32::from_sign_exp_fraction(1, 0, 1.0 + f64::EPSILON), 1.0);
+    /// ```
+    fn from_sign_exp_fraction(sign: i8, exp: Self::Exp, fraction: f64) -> Self;
 
-    const EXP_INF_NAN: i16 = Self::EXP_MAX + 1;
-    const EXP_ADJUST: i16 =
-        mask!(Self::NUM_EXP_BITS - 1) as i16 + Self::NUM_SIG_BITS as i16 - 1;
-    const EXP_MAX: i16 = mask!(Self::NUM_EXP_BITS) - 1 - Self::EXP_ADJUST ;
-    const EXP_MIN: i16 = -Self::EXP_ADJUST;
+    /// Decompose a fixed-size array of floats into an array
+    /// of [FloatParts], without heap allocation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let parts = f32::decompose_array([1.0, -2.0, 0.0, f32::MAX]);
+    /// assert_eq!(parts[1].sigbits, 1 << 23);
+    /// assert_eq!(parts[1].exp, -22);
+    /// assert_eq!(parts[1].sign, -1);
+    /// ```
+    fn decompose_array<const N: usize>(
+        arr: [Self; N],
+    ) -> [FloatParts<Self::SigBits, Self::Exp>; N] {
+        arr.map(|x| {
+            let (sigbits, exp, sign) = x.to_float_parts();
+            FloatParts { sigbits, exp, sign }
+        })
+    }
 
-    type SigBits = u64;
-    type Exp = i16;
+    /// An unsigned integer key whose ordering is the IEEE
+    /// 754 `totalOrder` ordering of floats, as used by
+    /// `total_cmp` in `std`: `-NaN < -Inf < ... < -0.0 <
+    /// 0.0 < ... < Inf < NaN`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!((-1.0f32).total_order_key() < (-0.0f32).total_order_key());
+    /// assert!((-0.0f32).total_order_key() < 0.0f32.total_order_key());
+    /// assert!(f32::INFINITY.total_order_key() < f32::NAN.total_order_key());
+    /// ```
+    fn total_order_key(self) -> Self::SigBits;
 
+    /// Self-check of the guarantee that
+    /// [ToFloatParts::total_order_key] is consistent with
+    /// float comparison: returns true iff `a < b` implies
+    /// that the key of `a` is less than the key of `b`.
+    ///
+    /// The strict comparison is needed because `0.0 <= -0.0`
+    /// even though `-0.0` has the smaller key. When either
+    /// operand is a NaN the implication holds vacuously.
+    /// This should never return false.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(f32::verify_ordering_invariant(-1.0, f32::from_bits(1)));
+    /// assert!(f32::verify_ordering_invariant(0.0, -0.0));
+    /// assert!(f32::verify_ordering_invariant(2.0, 1.0));
+    /// ```
+    fn verify_ordering_invariant(a: Self, b: Self) -> bool;
 
-    to_float_parts!{u64, i16}
-}
+    /// Return the "schoolbook" scientific form of a float:
+    /// a mantissa in `[1,2)`, an exponent and a sign, such
+    /// that the value is `sign * mantissa * 2^exp`.
+    /// Subnormals are normalized. This differs from
+    /// [ToFloatParts::to_float_parts], where the exponent is
+    /// relative to the binary point of an integer significand.
+    ///
+    /// Zeros give a mantissa of `0.0` and exponent `0`.
+    /// Infinities and NaNs give a mantissa of `inf` or `NaN`
+    /// and exponent [Self::EXP_INF_NAN].
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(6.0f32.to_scientific_parts(), (1.5, 2, 1));
+    /// assert_eq!((-0.375f32).to_scientific_parts(), (1.5, -2, -1));
+    /// assert_eq!(f32::from_bits(1).to_scientific_parts(), (1.0, -149, 1));
+    /// ```
+    fn to_scientific_parts(self) -> (f64, Self::Exp, i8);
 
-#[test]
-fn test_to_float_parts_f64() {
-    assert_eq!((1.0f64).to_float_parts(), (1 << 53, -52, 1));
-    let denorm = -f64::powf(2.0, -1023.0 - 2.0);
-    assert_eq!(denorm.to_float_parts(), (1 << 50, f64::EXP_MIN, -1));
-    assert_eq!(f64::NAN.to_float_parts().1, f64::EXP_INF_NAN);
+    /// True iff `self` is finite and has no nonzero
+    /// significand bits below the binary point: that is, it
+    /// is an integer. Zeros are integers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(4.0f32.is_integer());
+    /// assert!(!4.5f32.is_integer());
+    /// assert!(1.0e20f32.is_integer());
+    /// assert!(!f32::NAN.is_integer());
+    /// ```
+    fn is_integer(self) -> bool;
+
+    /// Reassemble floats from separate slices of signs,
+    /// exponents and sigbits, as by
+    /// [ToFloatParts::from_float_parts], writing them to
+    /// `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices are not all the same length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let mut out = [0.0f32; 2];
+    /// f32::from_float_parts_slice(&[1, -1], &[-23, -22], &[3 << 22, 1 << 23], &mut out);
+    /// assert_eq!(out, [1.5, -2.0]);
+    /// ```
+    fn from_float_parts_slice(
+        signs: &[i8],
+        exps: &[Self::Exp],
+        sigbits: &[Self::SigBits],
+        out: &mut [Self],
+    );
+
+    /// The relative spacing of floats at `self`: the ULP (the
+    /// gap to the next float away from zero) divided by
+    /// `|self|`. This is close to `2^-(NUM_SIG_BITS - 1)` for
+    /// normals, and grows toward `1.0` through the
+    /// subnormals.
+    ///
+    /// Zeros give infinity; infinities and NaNs give NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.relative_ulp(), f32::powf(2.0, -23.0));
+    /// assert!(1.9f32.relative_ulp() < f32::powf(2.0, -23.0));
+    /// assert_eq!(f32::from_bits(1).relative_ulp(), 1.0);
+    /// assert_eq!(f32::from_bits(4).relative_ulp(), 0.25);
+    /// ```
+    fn relative_ulp(self) -> Self;
+
+    /// Number of significand bits needed to the right of the
+    /// binary point to represent `self` exactly: the minimum
+    /// number of fractional bits for a fixed-point
+    /// accumulator holding it.
+    ///
+    /// This is zero for integers, up to `NUM_SIG_BITS - 1` for
+    /// values in `[1,2)`, and up to the full subnormal width
+    /// (149 for `f32`) for tiny values. Infinities and NaNs
+    /// give zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(0.25f32.fractional_bit_count(), 2);
+    /// assert_eq!(1.5f32.fractional_bit_count(), 1);
+    /// assert_eq!(8.0f32.fractional_bit_count(), 0);
+    /// assert_eq!(f32::from_bits(1).fractional_bit_count(), 149);
+    /// ```
+    fn fractional_bit_count(self) -> u32;
+
+    /// True iff `self` is neither infinite nor NaN, as
+    /// determined from the decomposed exponent.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(1.0f32.is_finite_parts());
+    /// assert!(f32::from_bits(1).is_finite_parts());
+    /// assert!((-0.0f32).is_finite_parts());
+    /// assert!(!f32::NEG_INFINITY.is_finite_parts());
+    /// assert!(!f32::NAN.is_finite_parts());
+    /// ```
+    fn is_finite_parts(self) -> bool;
+
+    /// Return `self` with its sign set to match `sign`:
+    /// negative if `sign` is negative, positive otherwise.
+    /// The magnitude, including any NaN payload, is
+    /// untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(2.5f32.with_sign(-1), -2.5);
+    /// assert_eq!((-2.5f32).with_sign(1), 2.5);
+    /// assert_eq!(0.0f32.with_sign(-1).to_bits(), (-0.0f32).to_bits());
+    /// let nan = f32::from_bits(0x7fc0_1234);
+    /// assert_eq!(nan.with_sign(-1).to_bits(), 0xffc0_1234);
+    /// ```
+    fn with_sign(self, sign: i8) -> Self;
+
+    /// The power of two `2^e`, where `e` is the unbiased
+    /// binary exponent of `self`: the largest power of two
+    /// not greater than `|self|`. This is the scale returned
+    /// by [ToFloatParts::split_scale], and is a subnormal for
+    /// small enough subnormal inputs.
+    ///
+    /// Zeros give `0.0`; infinities and NaNs give their
+    /// absolute value.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(6.0f32.exponent_as_float(), 4.0);
+    /// assert_eq!((-0.75f32).exponent_as_float(), 0.5);
+    /// assert_eq!(f32::from_bits(3).exponent_as_float(), f32::from_bits(2));
+    /// assert_eq!((-0.0f32).exponent_as_float().to_bits(), 0);
+    /// ```
+    fn exponent_as_float(self) -> Self;
+
+    /// Given a float, return the significand field, the
+    /// biased exponent field and the sign, exactly as stored.
+    ///
+    /// Unlike [ToFloatParts::to_float_parts], the implicit 1
+    /// is not made explicit, subnormal sigbits are not shifted,
+    /// and the exponent is not adjusted: a biased exponent of 0
+    /// marks a subnormal or zero, and all-ones marks an Inf or
+    /// NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.to_float_parts_raw(), (0, 127, 1));
+    /// let denorm = -f32::powf(2.0, -129.0);
+    /// assert_eq!(denorm.to_float_parts_raw(), (1 << 20, 0, -1));
+    /// assert_eq!(denorm.to_float_parts(), (1 << 21, f32::EXP_MIN, -1));
+    /// ```
+    fn to_float_parts_raw(self) -> (Self::SigBits, Self::Exp, i8);
+
+    /// Like [ToFloatParts::to_float_parts], but with the
+    /// sigbits left-justified: shifted so that the implicit-bit
+    /// position is the top bit of [Self::SigBits]. The
+    /// exponent and sign are unchanged, so the value is now
+    /// `sigbits * 2^(exp - (bits - NUM_SIG_BITS))` where `bits`
+    /// is the width of the sigbits type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.to_float_parts_left_justified(), (0x8000_0000, -23, 1));
+    /// assert_eq!(1.5f64.to_float_parts_left_justified().0, 0xc000_0000_0000_0000);
+    /// ```
+    fn to_float_parts_left_justified(self) -> (Self::SigBits, Self::Exp, i8);
+
+    /// Add two floats, also returning true iff the sum was
+    /// exact: that is, no rounding (or overflow) occurred. The
+    /// check is done on the exact sum of the aligned
+    /// significands.
+    ///
+    /// Operations on infinities and NaNs are reported as
+    /// exact.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(0.5f32.checked_add_exact(0.25), (0.75, true));
+    /// assert_eq!(0.1f32.checked_add_exact(0.2), (0.1 + 0.2, false));
+    /// assert_eq!(f32::MAX.checked_add_exact(f32::MAX), (f32::INFINITY, false));
+    /// ```
+    fn checked_add_exact(self, other: Self) -> (Self, bool);
+
+    /// Multiply two floats, also returning true iff the product
+    /// was exact: that is, no rounding, overflow or underflow
+    /// occurred. The check is done on the exact product of the
+    /// significands.
+    ///
+    /// Operations on infinities and NaNs are reported as
+    /// exact.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.5f32.checked_mul_exact(0.25), (0.375, true));
+    /// assert_eq!(0.1f32.checked_mul_exact(0.1), (0.1 * 0.1, false));
+    /// ```
+    fn checked_mul_exact(self, other: Self) -> (Self, bool);
+
+    /// The exponents `k` of the terms `2^k` that sum to
+    /// `|self|`: the binary-point-relative positions of the
+    /// set bits of the significand, from most to least
+    /// significant. Infinities and NaNs yield nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let bits: Vec<i16> = 3.5f32.significand_bit_positions().collect();
+    /// assert_eq!(bits, vec![1, 0, -1]);
+    /// assert_eq!(0.0f32.significand_bit_positions().count(), 0);
+    /// ```
+    fn significand_bit_positions(self) -> impl Iterator<Item = Self::Exp>;
+
+    /// Put hand-assembled parts into the canonical form
+    /// returned by [ToFloatParts::to_float_parts]: the sigbits
+    /// shifted and the exponent adjusted to match, so that the
+    /// leading 1 is at the implicit-bit position, or so that the
+    /// parts are a proper subnormal.
+    ///
+    /// The represented value is unchanged when the format can
+    /// hold it; otherwise it is rounded to nearest, ties to
+    /// even, possibly to an infinity or zero. Parts with
+    /// exponent [Self::EXP_INF_NAN] are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{FloatParts, ToFloatParts};
+    /// let p = f32::normalize_parts(FloatParts { sigbits: 3, exp: -1, sign: 1 });
+    /// assert_eq!(p, FloatParts { sigbits: 3 << 22, exp: -23, sign: 1 });
+    /// let p = f32::normalize_parts(FloatParts { sigbits: 4, exp: f32::EXP_MIN - 1, sign: -1 });
+    /// assert_eq!(p, FloatParts { sigbits: 2, exp: f32::EXP_MIN, sign: -1 });
+    /// ```
+    fn normalize_parts(
+        parts: FloatParts<Self::SigBits, Self::Exp>,
+    ) -> FloatParts<Self::SigBits, Self::Exp>;
+
+    /// Knuth's two-sum: return the rounded sum `s = self +
+    /// other` together with the exact rounding error `e`, so
+    /// that `s + e` is exactly the true sum. The error is
+    /// found by aligning the significands of the operands and
+    /// of `s`.
+    ///
+    /// If the sum is infinite or NaN, the error is not
+    /// meaningful and is returned as `0.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.two_sum(1.0e-20), (1.0, 1.0e-20));
+    /// let (s, e) = 1.0f32.two_sum(f32::EPSILON / 2.0 + f32::EPSILON / 8.0);
+    /// assert_eq!(s, 1.0 + f32::EPSILON);
+    /// assert_eq!(s as f64 + e as f64, 1.0 + 0.625 * f32::EPSILON as f64);
+    /// ```
+    fn two_sum(self, other: Self) -> (Self, Self);
+
+    /// A histogram bucket index for `self` by binary
+    /// exponent: the unbiased exponent `e` such that `2^e <=
+    /// |self| < 2^(e+1)` for normals, or one of the sentinels
+    /// [EXP_BUCKET_ZERO], [EXP_BUCKET_SUBNORMAL],
+    /// [EXP_BUCKET_INF] or [EXP_BUCKET_NAN]. The sentinels are
+    /// outside the exponent range of any format.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::*;
+    /// assert_eq!(6.0f32.exponent_bucket(), 2);
+    /// assert_eq!((-0.3f32).exponent_bucket(), -2);
+    /// assert_eq!(0.0f32.exponent_bucket(), EXP_BUCKET_ZERO);
+    /// assert_eq!(f32::from_bits(1).exponent_bucket(), EXP_BUCKET_SUBNORMAL);
+    /// assert_eq!(f32::NAN.exponent_bucket(), EXP_BUCKET_NAN);
+    /// ```
+    fn exponent_bucket(self) -> i32;
+
+    /// Parse a C99 hexadecimal float string, as produced by
+    /// `printf("%a")`: an optional sign, `0x`, hex digits
+    /// with an optional point, and an optional binary exponent
+    /// `p[+-]ddd`. The case-insensitive strings `inf`,
+    /// `infinity` and `nan`, with optional sign, are also
+    /// accepted.
+    ///
+    /// If the significand has more bits than `Self` can hold,
+    /// the result is rounded to nearest, ties to even, with
+    /// overflow giving an infinity and underflow a subnormal
+    /// or zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{ParseError, ToFloatParts};
+    /// assert_eq!(f32::from_hex_float_str("0x1.8p+1"), Ok(3.0));
+    /// assert_eq!(f32::from_hex_float_str("-0x.4"), Ok(-0.25));
+    /// assert_eq!(f32::from_hex_float_str("0x1p-149"), Ok(f32::from_bits(1)));
+    /// assert_eq!(f32::from_hex_float_str("0x1.000001p0"), Ok(1.0));
+    /// assert_eq!(f32::from_hex_float_str("-0x0p0").unwrap().to_bits(), 0x8000_0000);
+    /// assert_eq!(f32::from_hex_float_str("-INF"), Ok(f32::NEG_INFINITY));
+    /// assert_eq!(f32::from_hex_float_str("1.5"), Err(ParseError::Invalid));
+    /// ```
+    fn from_hex_float_str(s: &str) -> Result<Self, ParseError>;
+
+    /// True iff `self` is exactly halfway between two
+    /// integers: its lowest set significand bit is the one
+    /// just below the binary point.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(2.5f32.is_half_integer());
+    /// assert!((-3.5f32).is_half_integer());
+    /// assert!(!2.25f32.is_half_integer());
+    /// assert!(!2.0f32.is_half_integer());
+    /// ```
+    fn is_half_integer(self) -> bool;
+
+    /// Clamp the unbiased binary exponent `e` of `self` (as
+    /// returned by [ToFloatParts::to_scientific_parts]) into
+    /// `[lo, hi]`, keeping the significand and sign. The
+    /// magnitude of the result is thus in `[2^lo, 2^(hi+1))`.
+    ///
+    /// Subnormal inputs are normalized first. If `lo` is below
+    /// the normal range, a clamped result may be subnormal,
+    /// losing low significand bits to rounding; if `hi` is
+    /// above it, the result may be infinite. Zeros, infinities
+    /// and NaNs are returned unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!((1.5 * f32::powi(2.0, 40)).clamp_exponent(-4, 4), 24.0);
+    /// assert_eq!((-1.25 * f32::powi(2.0, -40)).clamp_exponent(-4, 4), -0.078125);
+    /// assert_eq!(3.0f32.clamp_exponent(-4, 4), 3.0);
+    /// ```
+    fn clamp_exponent(self, lo: Self::Exp, hi: Self::Exp) -> Self;
+
+    /// Return `self` with the bits of its stored significand
+    /// field (the `NUM_SIG_BITS - 1` bits below the exponent)
+    /// reversed. The sign and exponent fields are untouched,
+    /// so infinities stay infinite and NaNs stay NaN with
+    /// their payload bits reversed.
+    ///
+    /// Applying this twice gives back the original bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.5f32.reverse_significand_bits(), 1.0 + f32::powi(2.0, -23));
+    /// assert_eq!(f32::from_bits(0x7f80_0001).reverse_significand_bits().to_bits(), 0x7fc0_0000);
+    /// assert_eq!(f32::INFINITY.reverse_significand_bits(), f32::INFINITY);
+    /// ```
+    fn reverse_significand_bits(self) -> Self;
+
+    /// Add one to the stored significand field, wrapping
+    /// around within its `NUM_SIG_BITS - 1` bits, with the
+    /// sign and exponent fields left fixed.
+    ///
+    /// This differs from `next_up` only at the top of each
+    /// binade: where `next_up` would carry into the exponent,
+    /// this wraps back to the bottom of the same binade. Note
+    /// that with the Inf/NaN exponent the wrap moves between
+    /// infinity and NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.incr_significand_wrapping(), 1.0 + f32::EPSILON);
+    /// assert_eq!(1.0f32.next_down().incr_significand_wrapping(), 0.5);
+    /// assert_eq!((-2.0f32).incr_significand_wrapping(), -2.0 - 2.0 * f32::EPSILON);
+    /// ```
+    fn incr_significand_wrapping(self) -> Self;
+
+    /// For a finite float, return a [num_bigint::BigInt] and a
+    /// scale such that the value is exactly `bigint * 2^scale`,
+    /// or `None` for infinities and NaNs. This converts exactly
+    /// to rationals downstream at any exponent.
+    ///
+    /// Negative zero gives a zero `BigInt`: the sign is lost.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// use num_bigint::BigInt;
+    /// assert_eq!((-1.5f32).to_bigint_scaled(), Some((BigInt::from(-(3 << 22)), -23)));
+    /// assert_eq!(f32::NAN.to_bigint_scaled(), None);
+    /// ```
+    #[cfg(feature = "num-bigint")]
+    fn to_bigint_scaled(self) -> Option<(num_bigint::BigInt, i32)>;
+
+    /// The index `k` of the binade `[2^k, 2^(k+1))` holding
+    /// `|self|`: the unbiased binary exponent, for finite
+    /// nonzero values. Zeros, infinities and NaNs give `None`.
+    ///
+    /// Unlike a true floor of `log2`, subnormals are not
+    /// normalized: they all share the single bucket
+    /// [Self::EXP_MIN], which lies below every normal binade.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.binade(), Some(0));
+    /// assert_eq!((-0.75f32).binade(), Some(-1));
+    /// assert_eq!(f32::MIN_POSITIVE.binade(), Some(-126));
+    /// assert_eq!(f32::from_bits(1).binade(), Some(f32::EXP_MIN));
+    /// assert_eq!(0.0f32.binade(), None);
+    /// ```
+    fn binade(self) -> Option<Self::Exp>;
+
+    /// Decompose `self` as [ToFloatParts::to_float_parts]
+    /// does, passing the sigbits, exponent and sign straight
+    /// to `f` and returning its result.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let odd = 3.0f32.with_float_parts(|sigbits, exp, _| sigbits.trailing_zeros() as i16 + exp == 0);
+    /// assert!(odd);
+    /// ```
+    fn with_float_parts<R>(self, f: impl FnOnce(Self::SigBits, Self::Exp, i8) -> R) -> R;
+
+    /// Assemble a float from its sign bit, biased exponent
+    /// field and stored significand field (without the
+    /// implicit 1), exactly inverting
+    /// [ToFloatParts::to_float_parts_raw] up to the sign
+    /// representation.
+    ///
+    /// # Panics
+    /// Panics if `sign_bit` is not 0 or 1, or if a field is
+    /// wider than [Self::NUM_EXP_BITS] or `NUM_SIG_BITS - 1`
+    /// bits respectively.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_raw_fields(0, 127, 0), 1.0);
+    /// assert_eq!(f32::from_raw_fields(1, 128, 1 << 22), -3.0);
+    /// assert_eq!(f32::from_raw_fields(0, 0, 1).to_bits(), 1);
+    /// ```
+    fn from_raw_fields(sign_bit: u32, biased_exp: u32, sig_field: Self::SigBits) -> Self;
+
+    /// The number of ULP steps from `|self|` up to the next
+    /// power of two: the top of its binade. Zeros and
+    /// subnormals count up to the smallest normal, matching
+    /// the single subnormal bucket of [ToFloatParts::binade].
+    /// Returns `None` for infinities and NaNs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.ulps_to_next_binade(), Some(1 << 23));
+    /// assert_eq!(1.0f32.next_up().ulps_to_next_binade(), Some((1 << 23) - 1));
+    /// assert_eq!((-2.0f32).next_up().ulps_to_next_binade(), Some(1));
+    /// assert_eq!(0.0f32.ulps_to_next_binade(), Some(1 << 23));
+    /// ```
+    fn ulps_to_next_binade(self) -> Option<u64>;
+
+    /// Convert to `i64`, truncating toward zero, computed from
+    /// the parts. Values out of range saturate to `i64::MIN`
+    /// or `i64::MAX`, infinities included, and NaNs give 0:
+    /// the same result as an `as i64` cast.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!((-3.9f32).to_i64_saturating(), -3);
+    /// assert_eq!(1.0e30f32.to_i64_saturating(), i64::MAX);
+    /// assert_eq!(f32::NAN.to_i64_saturating(), 0);
+    /// ```
+    fn to_i64_saturating(self) -> i64;
+
+    /// Round `self` to `sig_bits` significant bits, to nearest
+    /// with ties to even, as if stored in a format with that
+    /// significand width and the exponent range of `Self`.
+    /// Values already that narrow, zeros, infinities and NaNs
+    /// are returned unchanged; rounding up past the largest
+    /// finite value gives an infinity.
+    ///
+    /// This is [ToFloatParts::round_as] with
+    /// [RoundingMode::NearestEven].
+    ///
+    /// # Panics
+    /// Panics if `sig_bits` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.1f32.round_trip_precision(4), 1.125);
+    /// assert_eq!((-1.2f32).round_trip_precision(2), -1.0);
+    /// assert_eq!(1.1f32.round_trip_precision(24), 1.1);
+    /// ```
+    fn round_trip_precision(self, sig_bits: u32) -> Self;
+
+    /// The unsigned key used by GPU radix sorts such as CUB
+    /// and Thrust: the bits with only the sign bit flipped for
+    /// positive values, and with every bit flipped for negative
+    /// ones. Sorting keys as unsigned integers sorts the floats
+    /// in the order of [ToFloatParts::total_order_key], which
+    /// with this crate's conventions is the very same key, and
+    /// is computed by it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.to_radix_sort_key(), 0xbf80_0000);
+    /// assert_eq!((-1.0f32).to_radix_sort_key(), 0x407f_ffff);
+    /// ```
+    fn to_radix_sort_key(self) -> Self::SigBits;
+
+    /// Invert [ToFloatParts::to_radix_sort_key], and so also
+    /// [ToFloatParts::total_order_key], recovering the float
+    /// bit-exactly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_radix_sort_key(0x407f_ffff), -1.0);
+    /// ```
+    fn from_radix_sort_key(key: Self::SigBits) -> Self;
+
+    /// Decompose in the convention of C's `frexp`: the value
+    /// is `sign * (sigbits / 2^NUM_SIG_BITS) * 2^exp`, with
+    /// the fraction in `[0.5, 1)`.
+    ///
+    /// For normals the sigbits are those of
+    /// [ToFloatParts::to_float_parts] and the exponent is
+    /// larger by exactly `NUM_SIG_BITS`, which is one more
+    /// than the unbiased exponent of
+    /// [ToFloatParts::to_scientific_parts]. Subnormals are
+    /// normalized to put the top bit at
+    /// [Self::IMPLICIT_BIT_POS]. Zeros give zero sigbits and
+    /// exponent, and infinities and NaNs are returned as by
+    /// [ToFloatParts::to_float_parts].
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.to_frexp_parts(), (1 << 23, 1, 1));
+    /// assert_eq!((-6.0f32).to_frexp_parts(), (3 << 22, 3, -1));
+    /// ```
+    fn to_frexp_parts(self) -> (Self::SigBits, Self::Exp, i8);
+
+    /// Like [ToFloatParts::to_float_parts], but with
+    /// subnormals flushed to zero as FTZ hardware does: they
+    /// decompose as a zero of the same sign, with exponent
+    /// [Self::EXP_MIN]. Other values are unaffected.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let denorm = -f32::from_bits(5);
+    /// assert_eq!(denorm.to_float_parts_ftz(), (0, f32::EXP_MIN, -1));
+    /// assert_eq!(1.0f32.to_float_parts_ftz(), 1.0f32.to_float_parts());
+    /// ```
+    fn to_float_parts_ftz(self) -> (Self::SigBits, Self::Exp, i8);
+
+    /// The number of nonnegative floats not greater than
+    /// `self`, counting `0.0` once: since nonnegative bit
+    /// patterns are ordered like their values, this is the
+    /// bits of `self` plus one. Negative zero counts as `0.0`.
+    /// Returns `None` for other negative values, infinities
+    /// and NaNs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(0.0f32.count_representable_below(), Some(1));
+    /// assert_eq!(f32::from_bits(2).count_representable_below(), Some(3));
+    /// assert_eq!(1.0f32.count_representable_below(), Some(0x3f80_0001));
+    /// assert_eq!((-1.0f32).count_representable_below(), None);
+    /// ```
+    fn count_representable_below(self) -> Option<u64>;
+
+    /// Pack the sign, exponent and significand into a `u128`
+    /// whose layout is the same for every format, so that
+    /// equal values in different formats give equal flat
+    /// forms:
+    ///
+    /// * bits 120–127: the sign, 1 for negative and 0 for
+    ///   positive;
+    /// * bits 104–119: the unbiased exponent of
+    ///   [ToFloatParts::to_scientific_parts] plus `0x8000`, or
+    ///   0 for zeros and `0xffff` for infinities and NaNs;
+    /// * bits 0–103: the significand, normalized so that its
+    ///   leading 1 (implicit or not) is at bit 103. For NaNs
+    ///   the payload sits just below, in bits 102 downward.
+    ///
+    /// Unlike the raw bits, subnormals are normalized and the
+    /// implicit bit is explicit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.to_flat_u128(), 0x0080_0080_0000_0000_0000_0000_0000_0000);
+    /// assert_eq!(1.0f32.to_flat_u128(), 1.0f64.to_flat_u128());
+    /// assert_eq!((-0.0f32).to_flat_u128(), 1 << 120);
+    /// ```
+    fn to_flat_u128(self) -> u128;
+
+    /// Invert [ToFloatParts::to_flat_u128]. A flat form from
+    /// a wider format is rounded to nearest, ties to even, and
+    /// a NaN payload keeps its high bits, with the quiet bit
+    /// set if otherwise none would remain.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::from_flat_u128((-2.5f32).to_flat_u128()), -2.5);
+    /// assert_eq!(f32::from_flat_u128(0.1f64.to_flat_u128()), 0.1f32);
+    /// ```
+    fn from_flat_u128(flat: u128) -> Self;
+
+    /// The subtraction analog of [ToFloatParts::two_sum]:
+    /// return the rounded difference `d = self - other`
+    /// together with the exact rounding error `e`, so that
+    /// `d + e` is exactly the true difference. Negation is
+    /// exact, so this is the two-sum of `self` and `-other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.two_diff(1.0e-20), (1.0, -1.0e-20));
+    /// assert_eq!(1.0f32.two_diff(1.0f32.next_down()), (f32::EPSILON / 2.0, 0.0));
+    /// ```
+    fn two_diff(self, other: Self) -> (Self, Self);
+
+    /// A canonical example of the category `cat`: `1.0` for
+    /// normals, the smallest positive subnormal, `0.0`,
+    /// positive infinity, and the positive quiet NaN with zero
+    /// payload.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// use std::num::FpCategory;
+    /// assert_eq!(f32::category_example(FpCategory::Normal), 1.0);
+    /// assert_eq!(f32::category_example(FpCategory::Subnormal).to_bits(), 1);
+    /// assert!(f32::category_example(FpCategory::Nan).is_nan());
+    /// ```
+    fn category_example(cat: std::num::FpCategory) -> Self;
+
+    /// The number of leading significand bits on which `self`
+    /// and `other` agree once aligned by exponent: roughly
+    /// `-log2` of their relative difference. Equal values
+    /// (with `0.0` equal to `-0.0`, and infinities equal to
+    /// themselves) give [Self::NUM_SIG_BITS].
+    ///
+    /// Values of differing sign, NaNs and mixes of finite and
+    /// infinite values agree on no bits. Note that values on
+    /// either side of a power of two also agree on no bits,
+    /// however close they are, since their leading bits are
+    /// at different positions.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.agreeing_bits(1.0 + f32::EPSILON), 23);
+    /// assert_eq!(1.0f32.agreeing_bits(1.5), 1);
+    /// assert_eq!(1.0f32.agreeing_bits(-1.0), 0);
+    /// ```
+    fn agreeing_bits(self, other: Self) -> u32;
+
+    /// Round `self` to `sig_bits` significant bits in
+    /// direction `mode`, as if stored in a format with that
+    /// significand width and the exponent range of `Self`.
+    /// Values already that narrow, zeros, infinities and NaNs
+    /// are returned unchanged; rounding away from zero past
+    /// the largest finite value gives an infinity.
+    ///
+    /// # Panics
+    /// Panics if `sig_bits` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::{RoundingMode, ToFloatParts};
+    /// assert_eq!(1.1f32.round_as(RoundingMode::NearestEven, 4), 1.125);
+    /// assert_eq!(1.1f32.round_as(RoundingMode::TowardZero, 4), 1.0);
+    /// assert_eq!((-1.1f32).round_as(RoundingMode::TowardNegative, 4), -1.125);
+    /// ```
+    fn round_as(self, mode: RoundingMode, sig_bits: u32) -> Self;
+
+    /// `self` divided by its own leading power of two: the
+    /// significand as a float in `[1, 2)`, with the sign kept.
+    /// The result has the bias as its exponent field.
+    /// Subnormals are normalized first. Zeros, infinities and
+    /// NaNs are returned unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(6.0f32.to_unit_mantissa(), 1.5);
+    /// assert_eq!((-0.3f32).to_unit_mantissa(), -1.2);
+    /// assert_eq!(f32::from_bits(3).to_unit_mantissa(), 1.5);
+    /// ```
+    fn to_unit_mantissa(self) -> Self;
+
+    /// The unbiased binary exponent `floor(log2(|self -
+    /// other|))` of the exact difference, found from the
+    /// aligned parts without rounding. Returns `None` if the
+    /// values are equal (zeros of either sign included) or
+    /// either is an infinity or NaN.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.difference_exponent(1.25), Some(-2));
+    /// assert_eq!(3.0f32.difference_exponent(-1.0), Some(2));
+    /// assert_eq!(1.0f32.difference_exponent(1.0), None);
+    /// ```
+    fn difference_exponent(self, other: Self) -> Option<Self::Exp>;
+
+    /// Round `self` to the nearest multiple of `2^exp`, with
+    /// ties to even multiples, by rounding away the
+    /// significand bits below `2^exp`. Values already on the
+    /// grid, infinities and NaNs are returned unchanged, and
+    /// the sign of a result rounded to zero is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.3f32.quantize_to_pow2(-2), 1.25);
+    /// assert_eq!(1.375f32.quantize_to_pow2(-2), 1.5);
+    /// assert_eq!(6.0f32.quantize_to_pow2(2), 8.0);
+    /// assert_eq!(10.0f32.quantize_to_pow2(2), 8.0);
+    /// ```
+    fn quantize_to_pow2(self, exp: Self::Exp) -> Self;
+
+    /// True iff `self` is finite and its significand, with
+    /// trailing zeros trimmed, has at most `max_bits`
+    /// significant bits: a short dyadic rational that converts
+    /// exactly to any format with that much precision. Zeros
+    /// have no significant bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(0.75f32.is_dyadic_short(2));
+    /// assert!(!0.75f32.is_dyadic_short(1));
+    /// assert!(!0.1f32.is_dyadic_short(4));
+    /// ```
+    fn is_dyadic_short(self, max_bits: u32) -> bool;
+
+    /// Split `self` into high and low parts with
+    /// non-overlapping significands, for double-double style
+    /// algorithms: `high + low == self` exactly, `high` keeps
+    /// only the top `ceil(NUM_SIG_BITS / 2)` significant bits,
+    /// and `low` holds the rest.
+    ///
+    /// The split truncates rather than rounds, so both parts
+    /// have the sign of `self` and, unlike Dekker's
+    /// multiplication-based split, `high` can never overflow.
+    /// Zeros, infinities and NaNs give `self` and a zero of
+    /// the same sign.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let x = 1.0f32 + f32::EPSILON;
+    /// assert_eq!(x.dekker_split(), (1.0, f32::EPSILON));
+    /// assert_eq!(0.5f32.dekker_split(), (0.5, 0.0));
+    /// ```
+    fn dekker_split(self) -> (Self, Self);
+
+    /// An estimate of the significant bits lost to
+    /// cancellation in computing `self - other`: how far the
+    /// leading bit of the exact difference, from
+    /// [ToFloatParts::difference_exponent], falls below that
+    /// of the larger operand. Equal values lose all
+    /// [Self::NUM_SIG_BITS] bits. Operands of opposite sign
+    /// (whose difference is a sum of magnitudes), zeros,
+    /// infinities and NaNs lose none.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.0f32.cancellation_bits(1.0 + f32::EPSILON), 23);
+    /// assert_eq!(1.0f32.cancellation_bits(0.75), 2);
+    /// assert_eq!(1.0e10f32.cancellation_bits(1.0), 0);
+    /// ```
+    fn cancellation_bits(self, other: Self) -> u32;
+
+    /// Each binade index, as returned by
+    /// [ToFloatParts::binade], in increasing order, with the
+    /// number of positive floats it holds. Every normal
+    /// binade holds `2^(NUM_SIG_BITS - 1)` values. The
+    /// subnormals all share the first entry, [Self::EXP_MIN],
+    /// which holds `2^(NUM_SIG_BITS - 1) - 1` values: zero is
+    /// not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// let weights = f32::binade_weights();
+    /// assert_eq!(weights.len(), 255);
+    /// assert_eq!(weights[0], (f32::EXP_MIN, (1 << 23) - 1));
+    /// assert_eq!(weights[1], (-126, 1 << 23));
+    /// assert_eq!(weights[254], (127, 1 << 23));
+    /// ```
+    fn binade_weights() -> Vec<(Self::Exp, u64)>;
+
+    /// The exact decimal value of the float, with every digit
+    /// of its terminating expansion: unlike `format!("{}")`,
+    /// which gives the shortest string that round-trips.
+    /// Integers have no decimal point. Infinities and NaNs are
+    /// written as `inf`, `-inf` and `NaN`, as by `Display`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(0.1f32.to_exact_decimal_string(), "0.100000001490116119384765625");
+    /// assert_eq!((-2.5f32).to_exact_decimal_string(), "-2.5");
+    /// assert_eq!(1.0e10f32.to_exact_decimal_string(), "10000000000");
+    /// ```
+    fn to_exact_decimal_string(self) -> String;
+
+    /// True iff formatting `self` with `digits` significant
+    /// decimal digits and parsing the result back gives the
+    /// same parts, and so the same bits. Any finite `f32`
+    /// survives 9 digits and any finite `f64` 17; infinities
+    /// survive any number of digits. NaNs survive only if they
+    /// have the payload that parsing `NaN` produces. Zero
+    /// digits never survive. Digits beyond the round-trip bound
+    /// are not formatted, so large `digits` are cheap.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert!(0.5f32.survives_decimal_roundtrip(1));
+    /// assert!(!(1.0f32 / 3.0).survives_decimal_roundtrip(7));
+    /// assert!(!1014618432.0f32.survives_decimal_roundtrip(8));
+    /// assert!(1014618432.0f32.survives_decimal_roundtrip(9));
+    /// ```
+    fn survives_decimal_roundtrip(self, digits: u32) -> bool;
+
+    /// The finite extremes `(-MAX, MAX)` of the type, built
+    /// from [Self::MAX_PARTS].
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(f32::representable_range(), (f32::MIN, f32::MAX));
+    /// ```
+    fn representable_range() -> (Self, Self);
+
+    /// The difference `exp(self) - exp(other)` of the
+    /// [ToFloatParts::to_float_parts] exponents: the left
+    /// shift that aligns the sigbits of `self` with those of
+    /// `other` for parts-domain addition. Positive means
+    /// `self` has the larger exponent. Returns `None` if either
+    /// value is an infinity or NaN.
+    ///
+    /// Since subnormal sigbits are stored doubled at
+    /// [Self::EXP_MIN], a subnormal and the smallest normal
+    /// are one shift apart.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(4.0f32.alignment_shift(1.0), Some(2));
+    /// assert_eq!(0.75f32.alignment_shift(3.0), Some(-2));
+    /// assert_eq!(1.0f32.alignment_shift(f32::NAN), None);
+    /// ```
+    fn alignment_shift(self, other: Self) -> Option<Self::Exp>;
+
+    /// Return `self` with the lowest `zeros` bits of its
+    /// [ToFloatParts::to_float_parts] sigbits cleared:
+    /// truncation toward zero onto a multiple of `2^(exp +
+    /// zeros)`. `zeros` is clamped to [Self::NUM_SIG_BITS], so
+    /// `NUM_SIG_BITS - 1` leaves a normal as the power of two
+    /// at its leading bit, and anything larger gives a zero of
+    /// the same sign. Infinities and NaNs are returned
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::ToFloatParts;
+    /// assert_eq!(1.75f32.with_trailing_zeros(22), 1.5);
+    /// assert_eq!((-1.75f32).with_trailing_zeros(23), -1.0);
+    /// assert_eq!(1.75f32.with_trailing_zeros(100), 0.0);
+    /// ```
+    fn with_trailing_zeros(self, zeros: u32) -> Self;
+}
+
+// Shift `x` right by `r` bits, rounding to nearest with
+// ties to even.
+fn shr_round_even(x: u128, r: u32) -> u128 {
+    if r == 0 {
+        return x;
+    }
+    if r > 128 {
+        return 0;
+    }
+    let q = x.checked_shr(r).unwrap_or(0);
+    let rem = x - q.checked_shl(r).unwrap_or(0);
+    let half = 1 << (r - 1);
+    if rem > half || (rem == half && q & 1 == 1) {
+        q + 1
+    } else {
+        q
+    }
+}
+
+// Shift the magnitude `x` of a value right by `r` bits,
+// rounding in direction `mode`; `negative` gives the sign of
+// the value for the directed modes.
+fn shr_round(x: u128, r: u32, mode: RoundingMode, negative: bool) -> u128 {
+    let up = match mode {
+        RoundingMode::NearestEven => return shr_round_even(x, r),
+        RoundingMode::TowardZero => false,
+        RoundingMode::TowardPositive => !negative,
+        RoundingMode::TowardNegative => negative,
+    };
+    let q = x.checked_shr(r).unwrap_or(0);
+    let inexact = q.checked_shl(r).unwrap_or(0) != x;
+    q + (inexact && up) as u128
+}
+
+// Add `a.0 * 2^a.1` and `b.0 * 2^b.1`, giving `sum * 2^exp`.
+// The operands are aligned on the smaller exponent. If
+// they are more than `guard` places apart, the smaller only
+// matters as a sticky bit well below the rounding position,
+// and is reduced to that; the returned flag is then false to
+// show that the sum is not exact.
+fn add_aligned(a: (i128, i32), b: (i128, i32), guard: i32) -> (i128, i32, bool) {
+    let ((a, ea), (b, eb)) = if a.1 >= b.1 { (a, b) } else { (b, a) };
+    let d = ea - eb;
+    if d <= guard {
+        ((a << d) + b, eb, true)
+    } else {
+        ((a << guard) + b.signum(), ea - guard, b == 0)
+    }
+}
+
+// True iff `s1 * 2^e1 == s2 * 2^e2`.
+fn same_value(s1: u128, e1: i32, s2: u128, e2: i32) -> bool {
+    if s1 == 0 || s2 == 0 {
+        return s1 == s2;
+    }
+    let (z1, z2) = (s1.trailing_zeros(), s2.trailing_zeros());
+    s1 >> z1 == s2 >> z2 && e1 + z1 as i32 == e2 + z2 as i32
+}
+
+// Parse the decimal binary exponent of a hex float,
+// saturating absurdly large values.
+fn parse_hex_float_exponent(s: &str) -> Result<i64, ParseError> {
+    let (neg, digits) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseError::BadExponent);
+    }
+    let e = digits
+        .bytes()
+        .fold(0i64, |e, b| (e * 10 + (b - b'0') as i64).min(1 << 40));
+    Ok(if neg { -e } else { e })
+}
+
+// The decimal digits of `n * factor^times`, computed with
+// base-10^9 limbs, least significant first.
+fn big_decimal_digits(n: u128, factor: u32, times: u32) -> String {
+    const BASE: u64 = 1_000_000_000;
+    let mut limbs = Vec::new();
+    let mut n = n;
+    while n > 0 {
+        limbs.push((n % BASE as u128) as u64);
+        n /= BASE as u128;
+    }
+    for _ in 0..times {
+        let mut carry = 0;
+        for limb in limbs.iter_mut() {
+            let x = *limb * factor as u64 + carry;
+            *limb = x % BASE;
+            carry = x / BASE;
+        }
+        if carry > 0 {
+            limbs.push(carry);
+        }
+    }
+
+    let Some((top, rest)) = limbs.split_last() else {
+        return "0".to_string();
+    };
+    let mut digits = top.to_string();
+    for limb in rest.iter().rev() {
+        digits.push_str(&format!("{:09}", limb));
+    }
+    digits
+}
+
+// Check the invariants of a decomposition done by
+// `to_float_parts`: the sign is ±1, the sigbits fit in the
+// significand width, the exponent is in range, and the
+// parts reconstruct the original bits. Called only with
+// debug assertions enabled, to catch broken format impls.
+/// True iff `x` is a NaN, judged from its parts.
+fn is_nan<T>(x: T) -> bool
+where
+    T: ToFloatParts,
+    T::SigBits: Into<u128>,
+{
+    let (sigbits, _, _) = x.to_float_parts();
+    !x.is_finite_parts() && sigbits.into() != 0
+}
+
+fn check_float_parts(
+    (sigbits, exp, sign): (u128, i32, i8),
+    num_sig_bits: u32,
+    exp_range: std::ops::RangeInclusive<i32>,
+    (bits, rebuilt): (u128, u128),
+) {
+    assert!(sign == 1 || sign == -1, "to_float_parts: bad sign {}", sign);
+    assert!(
+        sigbits >> num_sig_bits == 0,
+        "to_float_parts: sigbits {:#x} wider than {} bits",
+        sigbits,
+        num_sig_bits,
+    );
+    assert!(
+        exp_range.contains(&exp),
+        "to_float_parts: exponent {} outside {:?}",
+        exp,
+        exp_range,
+    );
+    assert_eq!(rebuilt, bits, "to_float_parts: parts do not reconstruct the float");
+}
+
+// The bit-level decomposition and reconstruction, kept apart
+// from `to_float_parts!` so that tests can run it against a
+// deliberately broken format.
+macro_rules! decompose_parts {
+    ($s:ty, $e:ty) => {
+        fn to_float_parts(self) -> (Self::SigBits, Self::Exp, i8) {
+            type S = $s;
+            type E = $e;
+
+            let ws = 8 * std::mem::size_of::<S>();
+            let ns = Self::NUM_SIG_BITS;
+            let ne = Self::NUM_EXP_BITS;
+
+            let bits = self.to_bits();
+
+            let sign = 1 - ((bits >> (ws - 2)) & 2) as i8;
+            let exp = (bits >> (ns - 1)) & mask!(ne);
+            let mut sigbits = bits & mask!(ns - 1);
+
+            let parts = if exp == mask!(ne) {
+                (sigbits, Self::EXP_INF_NAN, sign)
+            } else {
+                let is_denorm = exp == 0;
+                let exp = exp as E - Self::EXP_ADJUST;
+
+                sigbits <<= is_denorm as u32;
+                sigbits |= (!is_denorm as S) << (ns - 1);
+
+                (sigbits, exp, sign)
+            };
+
+            if cfg!(debug_assertions) {
+                let (sigbits, exp, sign) = parts;
+                let rebuilt = Self::from_float_parts(sigbits, exp, sign).to_bits();
+                check_float_parts(
+                    (sigbits as u128, exp as i32, sign),
+                    ns,
+                    Self::EXP_MIN as i32..=Self::EXP_INF_NAN as i32,
+                    (bits as u128, rebuilt as u128),
+                );
+            }
+
+            parts
+        }
+
+        fn from_float_parts(sigbits: Self::SigBits, exp: Self::Exp, sign: i8) -> Self {
+            type S = $s;
+
+            let ws = 8 * std::mem::size_of::<S>();
+            let ns = Self::NUM_SIG_BITS;
+            let ne = Self::NUM_EXP_BITS;
+
+            let sign = ((sign < 0) as S) << (ws - 1);
+            let (exp, sigbits) = if exp == Self::EXP_INF_NAN {
+                (mask!(ne), sigbits)
+            } else if exp == Self::EXP_MIN {
+                (0, sigbits >> 1)
+            } else {
+                ((exp + Self::EXP_ADJUST) as S, sigbits)
+            };
+
+            Self::from_bits(sign | (exp << (ns - 1)) | (sigbits & mask!(ns - 1)))
+        }
+    };
+}
+
+macro_rules! to_float_parts {
+    ($s:ty, $e:ty) => {
+        decompose_parts!{$s, $e}
+
+        fn from_float_parts_saturating(sigbits: Self::SigBits, exp: Self::Exp, sign: i8) -> Self {
+            if sigbits == 0 {
+                return Self::from_float_parts(0, Self::EXP_MIN, sign);
+            }
+            if exp > Self::EXP_MAX {
+                return Self::from_float_parts(0, Self::EXP_INF_NAN, sign);
+            }
+            if exp < Self::EXP_MIN {
+                let shift = (Self::EXP_MIN as i32 - exp as i32) as u32;
+                let sigbits = sigbits.checked_shr(shift).unwrap_or(0);
+                return Self::from_float_parts(sigbits, Self::EXP_MIN, sign);
+            }
+            Self::from_float_parts(sigbits, exp, sign)
+        }
+
+        fn from_normalized_significand(sig: u128, exp: i32, sign: i8) -> Self {
+            type S = $s;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+
+            if sig == 0 {
+                return Self::from_float_parts(0, Self::EXP_MIN, sign);
+            }
+
+            // Choose the exponent that puts the leading 1 at the
+            // implicit-bit position, but no lower than the
+            // exponent of the subnormal range.
+            let top = 127 - sig.leading_zeros() as i64;
+            let exp_normal = Self::EXP_MIN as i64 + 1;
+            let mut exp_target = (exp as i64 + top - (ns as i64 - 1)).max(exp_normal);
+
+            let shift = exp_target - exp as i64;
+            let mut sig = if shift >= 0 {
+                shr_round_even(sig, shift.min(u32::MAX as i64) as u32)
+            } else {
+                sig << -shift
+            };
+            if sig >> ns != 0 {
+                sig >>= 1;
+                exp_target += 1;
+            }
+
+            if exp_target > Self::EXP_MAX as i64 {
+                return Self::from_float_parts(0, Self::EXP_INF_NAN, sign);
+            }
+            if sig >> (ns - 1) == 0 {
+                return Self::from_float_parts((sig as S) << 1, Self::EXP_MIN, sign);
+            }
+            Self::from_float_parts(sig as S, exp_target as E, sign)
+        }
+
+        fn to_latex(self) -> String {
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+
+            if exp == Self::EXP_INF_NAN {
+                let s = match (sigbits, sign) {
+                    (0, 1) => r"\infty",
+                    (0, _) => r"-\infty",
+                    _ => r"\mathrm{NaN}",
+                };
+                return s.to_string();
+            }
+
+            // Subnormal sigbits are stored shifted left by one.
+            let (lead, fraction, exp) = if exp == Self::EXP_MIN {
+                (0, sigbits >> 1, Self::EXP_MIN + ns as E)
+            } else {
+                (1, sigbits & mask!(ns - 1), exp + (ns - 1) as E)
+            };
+
+            let digits = format!("{:0w$b}", fraction, w = (ns - 1) as usize);
+            let mut digits = digits.trim_end_matches('0');
+            if digits.is_empty() {
+                digits = "0";
+            }
+            let s = (sign < 0) as u8;
+
+            format!(r"(-1)^{{{}}} \times {}.{}_2 \times 2^{{{}}}", s, lead, digits, exp)
+        }
+
+        fn ulps_between(self, other: Self) -> Option<u64> {
+            type S = $s;
+
+            if self.is_nan() || other.is_nan() {
+                return None;
+            }
+
+            // Map the bits onto a line where adjacent floats
+            // differ by one and both zeros sit at the origin.
+            let ws = 8 * std::mem::size_of::<S>();
+            let key = |x: Self| {
+                let bits = x.to_bits();
+                let mag = (bits & mask!(ws - 1)) as i128;
+                if bits >> (ws - 1) == 0 {
+                    mag
+                } else {
+                    -mag
+                }
+            };
+
+            Some((key(self) - key(other)).unsigned_abs() as u64)
+        }
+
+        fn is_adjacent(self, other: Self) -> bool {
+            self.ulps_between(other) == Some(1)
+        }
+
+        fn significand_popcount(self) -> u32 {
+            self.to_float_parts().0.count_ones()
+        }
+
+        fn to_fixed_point(self, frac_bits: u32) -> Option<i64> {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return None;
+            }
+
+            let sig = sigbits as u128;
+            let shift = exp as i64 + frac_bits as i64;
+            let mag = if sig == 0 {
+                0
+            } else if shift >= 0 {
+                if shift >= sig.leading_zeros() as i64 {
+                    return None;
+                }
+                sig << shift
+            } else {
+                shr_round_even(sig, (-shift).min(u32::MAX as i64) as u32)
+            };
+
+            if sign < 0 {
+                if mag > 1 << 63 {
+                    return None;
+                }
+                Some((mag as i64).wrapping_neg())
+            } else {
+                i64::try_from(mag).ok()
+            }
+        }
+
+        fn from_fixed_point(value: i64, frac_bits: u32) -> Self {
+            let sign = if value < 0 { -1 } else { 1 };
+            let exp = -(frac_bits.min(i32::MAX as u32) as i32);
+            Self::from_normalized_significand(value.unsigned_abs() as u128, exp, sign)
+        }
+
+        fn split_scale(self) -> (Self, Self) {
+            type S = $s;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                return (self, 1.0);
+            }
+
+            let shift = sigbits.leading_zeros() - (S::BITS - ns);
+            let sigbits = sigbits << shift;
+            let exp = exp as i32 - shift as i32 + (ns - 1) as i32;
+
+            let mantissa = Self::from_float_parts(sigbits, -((ns - 1) as E), sign);
+            let scale = Self::from_normalized_significand(1, exp, 1);
+            (mantissa, scale)
+        }
+
+        fn try_cmp(self, other: Self) -> Result<std::cmp::Ordering, NanComparison> {
+            let is_nan = |x: Self| {
+                let (sigbits, exp, _) = x.to_float_parts();
+                exp == Self::EXP_INF_NAN && sigbits != 0
+            };
+            if is_nan(self) || is_nan(other) {
+                return Err(NanComparison);
+            }
+            Ok(self.partial_cmp(&other).unwrap())
+        }
+
+        fn midpoint_parts(self, other: Self) -> Self {
+            let ns = Self::NUM_SIG_BITS;
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            if ea == Self::EXP_INF_NAN || eb == Self::EXP_INF_NAN {
+                return (self + other) / 2.0;
+            }
+            if sa == 0 && sb == 0 {
+                return Self::from_float_parts(0, Self::EXP_MIN, signa.max(signb));
+            }
+
+            let (sum, exp, _) = add_aligned(
+                (signa as i128 * sa as i128, ea as i32),
+                (signb as i128 * sb as i128, eb as i32),
+                ns as i32 + 3,
+            );
+
+            let sign = if sum < 0 { -1 } else { 1 };
+            Self::from_normalized_significand(sum.unsigned_abs(), exp - 1, sign)
+        }
+
+        fn from_sign_exp_fraction(sign: i8, exp: Self::Exp, fraction: f64) -> Self {
+            let (sigbits, fexp, _) = fraction.to_float_parts();
+            if fexp == f64::EXP_INF_NAN {
+                return sign as Self * fraction.abs() as Self;
+            }
+            Self::from_normalized_significand(sigbits as u128, fexp as i32 + exp as i32, sign)
+        }
+
+        fn total_order_key(self) -> Self::SigBits {
+            type S = $s;
+
+            let ws = 8 * std::mem::size_of::<S>();
+            let bits = self.to_bits();
+            if bits >> (ws - 1) == 0 {
+                bits | (1 << (ws - 1))
+            } else {
+                !bits
+            }
+        }
+
+        fn verify_ordering_invariant(a: Self, b: Self) -> bool {
+            if a < b {
+                a.total_order_key() < b.total_order_key()
+            } else {
+                true
+            }
+        }
+
+        fn to_scientific_parts(self) -> (f64, Self::Exp, i8) {
+            type S = $s;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                let mantissa = if sigbits == 0 { f64::INFINITY } else { f64::NAN };
+                return (mantissa, exp, sign);
+            }
+            if sigbits == 0 {
+                return (0.0, 0, sign);
+            }
+
+            let shift = sigbits.leading_zeros() - (S::BITS - ns);
+            let mantissa = (sigbits << shift) as f64 / (1u64 << (ns - 1)) as f64;
+            (mantissa, exp + (ns - 1) as E - shift as E, sign)
+        }
+
+        fn is_integer(self) -> bool {
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return false;
+            }
+            sigbits == 0 || sigbits.trailing_zeros() as i32 >= -(exp as i32)
+        }
+
+        fn from_float_parts_slice(
+            signs: &[i8],
+            exps: &[Self::Exp],
+            sigbits: &[Self::SigBits],
+            out: &mut [Self],
+        ) {
+            let n = out.len();
+            assert!(
+                signs.len() == n && exps.len() == n && sigbits.len() == n,
+                "from_float_parts_slice: slice length mismatch",
+            );
+            for (i, x) in out.iter_mut().enumerate() {
+                *x = Self::from_float_parts(sigbits[i], exps[i], signs[i]);
+            }
+        }
+
+        fn relative_ulp(self) -> Self {
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return Self::NAN;
+            }
+
+            // The ULP is one unit of the sigbits, except that
+            // subnormal sigbits are stored shifted left by one.
+            let ulp = if exp == Self::EXP_MIN { 2.0 } else { 1.0 };
+            ulp / sigbits as Self
+        }
+
+        fn fractional_bit_count(self) -> u32 {
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                return 0;
+            }
+            let lsb = exp as i32 + sigbits.trailing_zeros() as i32;
+            (-lsb).max(0) as u32
+        }
+
+        fn is_finite_parts(self) -> bool {
+            self.to_float_parts().1 != Self::EXP_INF_NAN
+        }
+
+        fn with_sign(self, sign: i8) -> Self {
+            let (sigbits, exp, _) = self.to_float_parts();
+            Self::from_float_parts(sigbits, exp, sign)
+        }
+
+        fn exponent_as_float(self) -> Self {
+            if !self.is_finite_parts() || self == 0.0 {
+                return self.abs();
+            }
+            self.split_scale().1
+        }
+
+        fn to_float_parts_raw(self) -> (Self::SigBits, Self::Exp, i8) {
+            type S = $s;
+            type E = $e;
+
+            let ws = 8 * std::mem::size_of::<S>();
+            let ns = Self::NUM_SIG_BITS;
+            let ne = Self::NUM_EXP_BITS;
+
+            let bits = self.to_bits();
+            let sign = 1 - ((bits >> (ws - 2)) & 2) as i8;
+            let exp = (bits >> (ns - 1)) & mask!(ne);
+            (bits & mask!(ns - 1), exp as E, sign)
+        }
+
+        fn to_float_parts_left_justified(self) -> (Self::SigBits, Self::Exp, i8) {
+            type S = $s;
+
+            let (sigbits, exp, sign) = self.to_float_parts();
+            (sigbits << (S::BITS - Self::NUM_SIG_BITS), exp, sign)
+        }
+
+        fn checked_add_exact(self, other: Self) -> (Self, bool) {
+            let ns = Self::NUM_SIG_BITS;
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            if ea == Self::EXP_INF_NAN || eb == Self::EXP_INF_NAN {
+                return (self + other, true);
+            }
+            if sa == 0 && sb == 0 {
+                return (Self::from_float_parts(0, Self::EXP_MIN, signa.max(signb)), true);
+            }
+
+            let (sum, exp, aligned) = add_aligned(
+                (signa as i128 * sa as i128, ea as i32),
+                (signb as i128 * sb as i128, eb as i32),
+                ns as i32 + 3,
+            );
+            let sign = if sum < 0 { -1 } else { 1 };
+            let r = Self::from_normalized_significand(sum.unsigned_abs(), exp, sign);
+
+            let (rs, re, _) = r.to_float_parts();
+            let exact = aligned
+                && re != Self::EXP_INF_NAN
+                && same_value(rs as u128, re as i32, sum.unsigned_abs(), exp);
+            (r, exact)
+        }
+
+        fn checked_mul_exact(self, other: Self) -> (Self, bool) {
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            if ea == Self::EXP_INF_NAN || eb == Self::EXP_INF_NAN {
+                return (self * other, true);
+            }
+
+            let prod = sa as u128 * sb as u128;
+            let exp = ea as i32 + eb as i32;
+            let r = Self::from_normalized_significand(prod, exp, signa * signb);
+
+            let (rs, re, _) = r.to_float_parts();
+            let exact = re != Self::EXP_INF_NAN && same_value(rs as u128, re as i32, prod, exp);
+            (r, exact)
+        }
+
+        fn significand_bit_positions(self) -> impl Iterator<Item = Self::Exp> {
+            type S = $s;
+            type E = $e;
+
+            let (mut sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                sigbits = 0;
+            }
+            (0..S::BITS)
+                .rev()
+                .filter(move |&i| (sigbits >> i) & 1 == 1)
+                .map(move |i| i as E + exp)
+        }
+
+        fn normalize_parts(
+            parts: FloatParts<Self::SigBits, Self::Exp>,
+        ) -> FloatParts<Self::SigBits, Self::Exp> {
+            let FloatParts { sigbits, exp, sign } = parts;
+            if exp == Self::EXP_INF_NAN {
+                return parts;
+            }
+            let x = Self::from_normalized_significand(sigbits as u128, exp as i32, sign);
+            let (sigbits, exp, sign) = x.to_float_parts();
+            FloatParts { sigbits, exp, sign }
+        }
+
+        fn two_sum(self, other: Self) -> (Self, Self) {
+            let ns = Self::NUM_SIG_BITS;
+            let s = self + other;
+            // A sum of floats that rounds to zero is exactly zero.
+            if !s.is_finite_parts() || s == 0.0 {
+                return (s, 0.0);
+            }
+
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            let (sum, exp, aligned) = add_aligned(
+                (signa as i128 * sa as i128, ea as i32),
+                (signb as i128 * sb as i128, eb as i32),
+                ns as i32 + 3,
+            );
+            if !aligned {
+                // The smaller operand is far below the rounding
+                // position of the larger, which is thus the sum.
+                let e = if ea < eb { self } else { other };
+                return (s, e);
+            }
+
+            let (ss, es, signs) = s.to_float_parts();
+            let (err, exp, _) = add_aligned(
+                (sum, exp),
+                (-(signs as i128) * ss as i128, es as i32),
+                i32::MAX,
+            );
+            let sign = if err < 0 { -1 } else { 1 };
+            (s, Self::from_normalized_significand(err.unsigned_abs(), exp, sign))
+        }
+
+        fn exponent_bucket(self) -> i32 {
+            let (sigbits, exp, _) = self.to_float_parts();
+            match (sigbits, exp) {
+                (0, Self::EXP_INF_NAN) => EXP_BUCKET_INF,
+                (_, Self::EXP_INF_NAN) => EXP_BUCKET_NAN,
+                (0, _) => EXP_BUCKET_ZERO,
+                (_, Self::EXP_MIN) => EXP_BUCKET_SUBNORMAL,
+                _ => exp as i32 + Self::NUM_SIG_BITS as i32 - 1,
+            }
+        }
+
+        fn from_hex_float_str(s: &str) -> Result<Self, ParseError> {
+            let (sign, s) = match s.as_bytes().first() {
+                Some(b'-') => (-1, &s[1..]),
+                Some(b'+') => (1, &s[1..]),
+                _ => (1, s),
+            };
+            if s.eq_ignore_ascii_case("inf") || s.eq_ignore_ascii_case("infinity") {
+                return Ok(Self::from_float_parts(0, Self::EXP_INF_NAN, sign));
+            }
+            if s.eq_ignore_ascii_case("nan") {
+                return Ok(Self::NAN.with_sign(sign));
+            }
+            let s = s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .ok_or(ParseError::Invalid)?;
+            let (digits, exp) = match s.find(['p', 'P']) {
+                Some(i) => (&s[..i], Some(&s[i + 1..])),
+                None => (s, None),
+            };
+
+            let mut exp: i64 = match exp {
+                Some(e) => parse_hex_float_exponent(e)?,
+                None => 0,
+            };
+            let mut sig: u128 = 0;
+            let mut ndigits = 0;
+            let mut seen_point = false;
+            for c in digits.chars() {
+                if c == '.' && !seen_point {
+                    seen_point = true;
+                    continue;
+                }
+                let d = c.to_digit(16).ok_or(ParseError::Invalid)? as u128;
+                ndigits += 1;
+                if sig >> 120 == 0 {
+                    sig = (sig << 4) | d;
+                    if seen_point {
+                        exp -= 4;
+                    }
+                } else {
+                    // Too many bits to hold: the excess only
+                    // matters as a sticky bit for rounding.
+                    sig |= (d != 0) as u128;
+                    if !seen_point {
+                        exp += 4;
+                    }
+                }
+            }
+            if ndigits == 0 {
+                return Err(ParseError::NoDigits);
+            }
+
+            let exp = exp.clamp(-(1 << 20), 1 << 20) as i32;
+            Ok(Self::from_normalized_significand(sig, exp, sign))
+        }
+
+        fn is_half_integer(self) -> bool {
+            let (sigbits, exp, _) = self.to_float_parts();
+            exp != Self::EXP_INF_NAN
+                && sigbits != 0
+                && sigbits.trailing_zeros() as i32 + exp as i32 == -1
+        }
+
+        fn clamp_exponent(self, lo: Self::Exp, hi: Self::Exp) -> Self {
+            type S = $s;
+
+            assert!(lo <= hi, "clamp_exponent: lo > hi");
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                return self;
+            }
+
+            let shift = sigbits.leading_zeros() - (S::BITS - ns);
+            let e = exp as i32 + (ns - 1) as i32 - shift as i32;
+            let e = e.clamp(lo as i32, hi as i32);
+            Self::from_normalized_significand((sigbits << shift) as u128, e - (ns - 1) as i32, sign)
+        }
+
+        fn reverse_significand_bits(self) -> Self {
+            type S = $s;
+
+            let nf = Self::NUM_SIG_BITS - 1;
+            let bits = self.to_bits();
+            let field = bits & mask!(nf);
+            Self::from_bits((bits & !mask!(nf)) | (field.reverse_bits() >> (S::BITS - nf)))
+        }
+
+        fn incr_significand_wrapping(self) -> Self {
+            let nf = Self::NUM_SIG_BITS - 1;
+            let bits = self.to_bits();
+            let field = bits.wrapping_add(1) & mask!(nf);
+            Self::from_bits((bits & !mask!(nf)) | field)
+        }
+
+        #[cfg(feature = "num-bigint")]
+        fn to_bigint_scaled(self) -> Option<(num_bigint::BigInt, i32)> {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return None;
+            }
+            let bigint = num_bigint::BigInt::from(sigbits);
+            Some((if sign < 0 { -bigint } else { bigint }, exp as i32))
+        }
+
+        fn binade(self) -> Option<Self::Exp> {
+            type E = $e;
+
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                None
+            } else if exp == Self::EXP_MIN {
+                Some(Self::EXP_MIN)
+            } else {
+                Some(exp + (Self::NUM_SIG_BITS - 1) as E)
+            }
+        }
+
+        fn with_float_parts<R>(self, f: impl FnOnce(Self::SigBits, Self::Exp, i8) -> R) -> R {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            f(sigbits, exp, sign)
+        }
+
+        fn from_raw_fields(sign_bit: u32, biased_exp: u32, sig_field: Self::SigBits) -> Self {
+            type S = $s;
+
+            let ns = Self::NUM_SIG_BITS;
+            let ne = Self::NUM_EXP_BITS;
+            assert!(sign_bit <= 1, "from_raw_fields: sign bit {} not 0 or 1", sign_bit);
+            assert!(biased_exp >> ne == 0, "from_raw_fields: exponent field {:#x} too wide", biased_exp);
+            assert!(sig_field >> (ns - 1) == 0, "from_raw_fields: significand field {:#x} too wide", sig_field);
+
+            let bits = (sign_bit as S) << (S::BITS - 1) | (biased_exp as S) << (ns - 1) | sig_field;
+            Self::from_bits(bits)
+        }
+
+        fn ulps_to_next_binade(self) -> Option<u64> {
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return None;
+            }
+            let steps = (1 << Self::NUM_SIG_BITS) - sigbits as u64;
+            Some(if exp == Self::EXP_MIN { steps >> 1 } else { steps })
+        }
+
+        fn to_i64_saturating(self) -> i64 {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            let sigbits = sigbits as i128;
+            let magnitude = if exp == Self::EXP_INF_NAN {
+                if sigbits != 0 {
+                    return 0;
+                }
+                i128::MAX
+            } else if exp >= 64 {
+                if sigbits == 0 { 0 } else { i128::MAX }
+            } else if exp >= 0 {
+                sigbits << exp
+            } else {
+                sigbits.checked_shr(-(exp as i32) as u32).unwrap_or(0)
+            };
+            let value = if sign < 0 { -magnitude } else { magnitude };
+            value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+        }
+
+        fn round_trip_precision(self, sig_bits: u32) -> Self {
+            self.round_as(RoundingMode::NearestEven, sig_bits)
+        }
+
+        fn to_radix_sort_key(self) -> Self::SigBits {
+            self.total_order_key()
+        }
+
+        fn from_radix_sort_key(key: Self::SigBits) -> Self {
+            type S = $s;
+
+            // Keys of positive values have the sign bit set.
+            let flip = if key >> (S::BITS - 1) == 1 { 1 << (S::BITS - 1) } else { S::MAX };
+            Self::from_bits(key ^ flip)
+        }
+
+        fn to_frexp_parts(self) -> (Self::SigBits, Self::Exp, i8) {
+            type S = $s;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return (sigbits, exp, sign);
+            }
+            if sigbits == 0 {
+                return (0, 0, sign);
+            }
+            let shift = sigbits.leading_zeros() - (S::BITS - ns);
+            (sigbits << shift, exp + ns as E - shift as E, sign)
+        }
+
+        fn to_float_parts_ftz(self) -> (Self::SigBits, Self::Exp, i8) {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_MIN {
+                (0, exp, sign)
+            } else {
+                (sigbits, exp, sign)
+            }
+        }
+
+        fn count_representable_below(self) -> Option<u64> {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || (sign < 0 && sigbits != 0) {
+                return None;
+            }
+            Some(self.abs().to_bits() as u64 + 1)
+        }
+
+        fn to_flat_u128(self) -> u128 {
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            let sign = ((sign < 0) as u128) << 120;
+            let sigbits = sigbits as u128;
+            if exp == Self::EXP_INF_NAN {
+                return sign | 0xffff << 104 | sigbits << (104 - ns);
+            }
+            if sigbits == 0 {
+                return sign;
+            }
+
+            let width = u128::BITS - sigbits.leading_zeros();
+            let e = exp as i32 + width as i32 - 1;
+            sign | ((e + 0x8000) as u128) << 104 | sigbits << (104 - width)
+        }
+
+        fn from_flat_u128(flat: u128) -> Self {
+            type S = $s;
+
+            let ns = Self::NUM_SIG_BITS;
+            let sign = if (flat >> 120) & 1 != 0 { -1 } else { 1 };
+            let ef = (flat >> 104) & 0xffff;
+            let sig = flat & mask!(104);
+            if ef == 0xffff {
+                let mut payload = (sig >> (104 - ns)) as S & mask!(ns - 1);
+                if sig != 0 && payload == 0 {
+                    payload = 1 << (ns - 2);
+                }
+                return Self::from_float_parts(payload, Self::EXP_INF_NAN, sign);
+            }
+            if ef == 0 {
+                return Self::from_float_parts(0, Self::EXP_MIN, sign);
+            }
+            Self::from_normalized_significand(sig, ef as i32 - 0x8000 - 103, sign)
+        }
+
+        fn two_diff(self, other: Self) -> (Self, Self) {
+            self.two_sum(-other)
+        }
+
+        fn category_example(cat: std::num::FpCategory) -> Self {
+            use std::num::FpCategory;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp) = match cat {
+                FpCategory::Normal => (1 << (ns - 1), -((ns - 1) as E)),
+                FpCategory::Subnormal => (2, Self::EXP_MIN),
+                FpCategory::Zero => (0, Self::EXP_MIN),
+                FpCategory::Infinite => (0, Self::EXP_INF_NAN),
+                FpCategory::Nan => (1 << (ns - 2), Self::EXP_INF_NAN),
+            };
+            Self::from_float_parts(sigbits, exp, 1)
+        }
+
+        fn agreeing_bits(self, other: Self) -> u32 {
+            let ns = Self::NUM_SIG_BITS;
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            let (sa, ea, sb, eb) = (sa as u128, ea as i32, sb as u128, eb as i32);
+            match self.try_cmp(other) {
+                Err(_) => return 0,
+                Ok(std::cmp::Ordering::Equal) => return ns,
+                Ok(_) => (),
+            }
+            if signa != signb
+                || sa == 0
+                || sb == 0
+                || ea == Self::EXP_INF_NAN as i32
+                || eb == Self::EXP_INF_NAN as i32
+            {
+                return 0;
+            }
+
+            // Put the highest leading bit of the two at bit 127.
+            let top = |s: u128, e: i32| e + (u128::BITS - s.leading_zeros()) as i32 - 1;
+            let t = top(sa, ea).max(top(sb, eb));
+            let align = |s: u128, e: i32| {
+                let k = 127 - t + e;
+                if k >= 0 { s << k } else { s.checked_shr(-k as u32).unwrap_or(0) }
+            };
+            (align(sa, ea) ^ align(sb, eb)).leading_zeros().min(ns)
+        }
+
+        fn round_as(self, mode: RoundingMode, sig_bits: u32) -> Self {
+            assert!(sig_bits > 0, "round_as: zero significand width");
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                return self;
+            }
+
+            let sig = sigbits as u128;
+            let width = u128::BITS - sig.leading_zeros();
+            if width <= sig_bits {
+                return self;
+            }
+            let r = width - sig_bits;
+            let sig = shr_round(sig, r, mode, sign < 0);
+            Self::from_normalized_significand(sig, exp as i32 + r as i32, sign)
+        }
+
+        fn to_unit_mantissa(self) -> Self {
+            type S = $s;
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN || sigbits == 0 {
+                return self;
+            }
+            let shift = sigbits.leading_zeros() - (S::BITS - ns);
+            Self::from_float_parts(sigbits << shift, -((ns - 1) as E), sign)
+        }
+
+        fn difference_exponent(self, other: Self) -> Option<Self::Exp> {
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let (sa, ea, signa) = self.to_float_parts();
+            let (sb, eb, signb) = other.to_float_parts();
+            if ea == Self::EXP_INF_NAN || eb == Self::EXP_INF_NAN {
+                return None;
+            }
+
+            // A far smaller operand is reduced to a sticky bit,
+            // which cannot move the leading bit of the difference.
+            let (diff, exp, _) = add_aligned(
+                (signa as i128 * sa as i128, ea as i32),
+                (-(signb as i128) * sb as i128, eb as i32),
+                ns as i32 + 3,
+            );
+            if diff == 0 {
+                return None;
+            }
+            let width = u128::BITS - diff.unsigned_abs().leading_zeros();
+            Some((exp + width as i32 - 1) as E)
+        }
+
+        fn quantize_to_pow2(self, exp: Self::Exp) -> Self {
+            let (sigbits, e, sign) = self.to_float_parts();
+            if e == Self::EXP_INF_NAN || e >= exp {
+                return self;
+            }
+            let r = (exp as i32 - e as i32) as u32;
+            Self::from_normalized_significand(shr_round_even(sigbits as u128, r), exp as i32, sign)
+        }
+
+        fn is_dyadic_short(self, max_bits: u32) -> bool {
+            type S = $s;
+
+            let (sigbits, exp, _) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return false;
+            }
+            let bits = (S::BITS - sigbits.leading_zeros()).saturating_sub(sigbits.trailing_zeros());
+            bits <= max_bits
+        }
+
+        fn dekker_split(self) -> (Self, Self) {
+            let h = Self::NUM_SIG_BITS.div_ceil(2);
+            let (sigbits, exp, sign) = self.to_float_parts();
+            let zero = Self::from_float_parts(0, Self::EXP_MIN, sign);
+            if exp == Self::EXP_INF_NAN {
+                return (self, zero);
+            }
+
+            let sig = sigbits as u128;
+            let width = u128::BITS - sig.leading_zeros();
+            if width <= h {
+                return (self, zero);
+            }
+            let r = width - h;
+            let high = Self::from_normalized_significand(sig >> r << r, exp as i32, sign);
+            let low = Self::from_normalized_significand(sig & mask!(r), exp as i32, sign);
+            (high, low)
+        }
+
+        fn cancellation_bits(self, other: Self) -> u32 {
+            let ns = Self::NUM_SIG_BITS;
+            let (sa, ea, signa) = self.to_frexp_parts();
+            let (sb, eb, signb) = other.to_frexp_parts();
+            if signa != signb
+                || sa == 0
+                || sb == 0
+                || ea == Self::EXP_INF_NAN
+                || eb == Self::EXP_INF_NAN
+            {
+                return 0;
+            }
+            let Some(diff_exp) = self.difference_exponent(other) else {
+                return ns;
+            };
+            let top = ea.max(eb) as i32 - 1;
+            (top - diff_exp as i32).clamp(0, ns as i32) as u32
+        }
+
+        fn binade_weights() -> Vec<(Self::Exp, u64)> {
+            type E = $e;
+
+            let ns = Self::NUM_SIG_BITS;
+            let count = 1u64 << (ns - 1);
+            let lo = Self::EXP_MIN + ns as E;
+            let hi = Self::EXP_MAX + (ns - 1) as E;
+            std::iter::once((Self::EXP_MIN, count - 1))
+                .chain((lo..=hi).map(|k| (k, count)))
+                .collect()
+        }
+
+        fn to_exact_decimal_string(self) -> String {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                let s = if sigbits != 0 { "NaN" } else if sign < 0 { "-inf" } else { "inf" };
+                return s.to_string();
+            }
+            let neg = if sign < 0 { "-" } else { "" };
+            if sigbits == 0 {
+                return format!("{}0", neg);
+            }
+
+            let z = sigbits.trailing_zeros();
+            let (sig, exp) = ((sigbits >> z) as u128, exp as i32 + z as i32);
+            if exp >= 0 {
+                return format!("{}{}", neg, big_decimal_digits(sig, 2, exp as u32));
+            }
+
+            // sig * 2^-k is sig * 5^k / 10^k.
+            let k = -exp as usize;
+            let digits = big_decimal_digits(sig, 5, k as u32);
+            let digits = format!("{:0>w$}", digits, w = k + 1);
+            let (int, frac) = digits.split_at(digits.len() - k);
+            format!("{}{}.{}", neg, int, frac)
+        }
+
+        fn survives_decimal_roundtrip(self, digits: u32) -> bool {
+            if digits == 0 {
+                return false;
+            }
+            // Enough digits to round-trip any finite value: one
+            // more than the decimal digits spanned by the
+            // significand, using 0.30103 for log10(2).
+            let bound = 1 + (Self::NUM_SIG_BITS * 30103).div_ceil(100_000);
+            if digits >= bound && self.is_finite_parts() {
+                return true;
+            }
+            let digits = digits.min(bound);
+            let s = format!("{:.*e}", digits as usize - 1, self);
+            match s.parse::<Self>() {
+                Ok(x) => x.to_float_parts() == self.to_float_parts(),
+                Err(_) => false,
+            }
+        }
+
+        fn representable_range() -> (Self, Self) {
+            let (sigbits, exp, _) = Self::MAX_PARTS;
+            (Self::from_float_parts(sigbits, exp, -1), Self::from_float_parts(sigbits, exp, 1))
+        }
+
+        fn alignment_shift(self, other: Self) -> Option<Self::Exp> {
+            let (_, ea, _) = self.to_float_parts();
+            let (_, eb, _) = other.to_float_parts();
+            if ea == Self::EXP_INF_NAN || eb == Self::EXP_INF_NAN {
+                return None;
+            }
+            Some(ea - eb)
+        }
+
+        fn with_trailing_zeros(self, zeros: u32) -> Self {
+            let (sigbits, exp, sign) = self.to_float_parts();
+            if exp == Self::EXP_INF_NAN {
+                return self;
+            }
+            let zeros = zeros.min(Self::NUM_SIG_BITS);
+            let sigbits = sigbits >> zeros << zeros;
+            if sigbits == 0 {
+                return Self::from_float_parts(0, Self::EXP_MIN, sign);
+            }
+            Self::from_float_parts(sigbits, exp, sign)
+        }
+    };
+}
+
+
+impl ToFloatParts for f32 {
+    const NUM_SIG_BITS: u32 = f32::MANTISSA_DIGITS;
+    // Why is there no constant for this in `std`?
+    const NUM_EXP_BITS: u32 = 8;
+
+    const EXP_INF_NAN: i16 = Self::EXP_MAX + 1;
+    const EXP_ADJUST: i16 =
+        mask!(Self::NUM_EXP_BITS - 1) as i16 + Self::NUM_SIG_BITS as i16 - 1;
+    const EXP_MAX: i16 = mask!(Self::NUM_EXP_BITS) - 1 - Self::EXP_ADJUST ;
+    const EXP_MIN: i16 = -Self::EXP_ADJUST;
+    const MAX_PARTS: (u32, i16, i8) = (mask!(Self::NUM_SIG_BITS), Self::EXP_MAX, 1);
+    const EPSILON: f32 = f32::from_bits(
+        (mask!(Self::NUM_EXP_BITS - 1) - (Self::NUM_SIG_BITS - 1)) << (Self::NUM_SIG_BITS - 1)
+    );
+
+    type SigBits = u32;
+    type Exp = i16;
+
+    to_float_parts!{u32, i16}
+}
+
+impl ToFloatParts for f64 {
+    const NUM_SIG_BITS: u32 = f64::MANTISSA_DIGITS;
+    // Why is there no constant for this in `std`?
+    const NUM_EXP_BITS: u32 = 11;
+
+    const EXP_INF_NAN: i16 = Self::EXP_MAX + 1;
+    const EXP_ADJUST: i16 =
+        mask!(Self::NUM_EXP_BITS - 1) as i16 + Self::NUM_SIG_BITS as i16 - 1;
+    const EXP_MAX: i16 = mask!(Self::NUM_EXP_BITS) - 1 - Self::EXP_ADJUST ;
+    const EXP_MIN: i16 = -Self::EXP_ADJUST;
+    const MAX_PARTS: (u64, i16, i8) = (mask!(Self::NUM_SIG_BITS), Self::EXP_MAX, 1);
+    const EPSILON: f64 = f64::from_bits(
+        ((mask!(Self::NUM_EXP_BITS - 1) - (Self::NUM_SIG_BITS - 1)) as u64) << (Self::NUM_SIG_BITS - 1)
+    );
+
+    type SigBits = u64;
+    type Exp = i16;
+
+
+    to_float_parts!{u64, i16}
+}
+
+// Conversions from [FloatParts].
+macro_rules! from_float_parts_struct {
+    ($f:ty, $s:ty, $e:ty) => {
+        /// Rebuild a float from its [FloatParts]. Exponents
+        /// outside the representable range saturate, as with
+        /// [ToFloatParts::from_float_parts_saturating], except
+        /// that [ToFloatParts::EXP_INF_NAN] is passed through so
+        /// that NaNs survive.
+        impl From<FloatParts<$s, $e>> for $f {
+            fn from(parts: FloatParts<$s, $e>) -> Self {
+                let FloatParts { sigbits, exp, sign } = parts;
+                if exp == Self::EXP_INF_NAN {
+                    Self::from_float_parts(sigbits, exp, sign)
+                } else {
+                    Self::from_float_parts_saturating(sigbits, exp, sign)
+                }
+            }
+        }
+    };
+}
+
+from_float_parts_struct!{f32, u32, i16}
+from_float_parts_struct!{f64, u64, i16}
+
+// Fuzzing support for [FloatParts].
+#[cfg(feature = "arbitrary")]
+macro_rules! arbitrary_float_parts {
+    ($f:ty, $s:ty, $e:ty) => {
+        /// Generate parts that pass [FloatParts::validate] for
+        /// the matching float type, so that fuzz targets always
+        /// get something that reconstructs: the exponent is in
+        /// range, the sigbits are masked to the significand with
+        /// the implicit bit set exactly for normal exponents, and
+        /// the sign is ±1.
+        impl<'a> arbitrary::Arbitrary<'a> for FloatParts<$s, $e> {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                type S = $s;
+
+                let ns = <$f>::NUM_SIG_BITS;
+                let implicit: S = 1 << (ns - 1);
+
+                let exp = u.int_in_range(<$f>::EXP_MIN..=<$f>::EXP_INF_NAN)?;
+                let sigbits = u.arbitrary::<S>()? & mask!(ns);
+                let sigbits = if exp == <$f>::EXP_MIN {
+                    sigbits & !1
+                } else if exp == <$f>::EXP_INF_NAN {
+                    sigbits & !implicit
+                } else {
+                    sigbits | implicit
+                };
+                let sign = if u.arbitrary()? { -1 } else { 1 };
+                Ok(FloatParts { sigbits, exp, sign })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "arbitrary")]
+arbitrary_float_parts!{f32, u32, i16}
+#[cfg(feature = "arbitrary")]
+arbitrary_float_parts!{f64, u64, i16}
+
+/// The parts of the float nearest to `value` in the format
+/// `fmt`, rounding to nearest with ties to even, in the
+/// [ToFloatParts::to_float_parts] convention for that format:
+/// the value is `sigbits * 2^exp`, with the exponent
+/// adjustment `2^(num_exp_bits - 1) - 1 + num_sig_bits - 1`
+/// and subnormal sigbits doubled at the minimum exponent.
+///
+/// Out-of-range values become an infinity, or the largest
+/// finite value in formats without infinities (infinite
+/// inputs included). Tiny values become subnormals or zeros.
+/// NaNs become the quiet NaN with zero payload, or in formats
+/// without infinities the one NaN, with all-ones sigbits.
+///
+/// # Panics
+/// Panics unless `fmt` has 2 to 113 significant bits and 2
+/// to 20 exponent bits.
+///
+/// # Examples
+/// ```
+/// # use float_parts::{nearest_in_format, FloatFormat};
+/// let fmt = FloatFormat { num_sig_bits: 5, num_exp_bits: 5, has_infinity: true };
+/// // 0.1 = 1.1001 1001...b * 2^-4, rounding up to 1.1010b * 2^-4.
+/// assert_eq!(nearest_in_format(0.1, fmt), (0b11010, -8, 1));
+/// assert_eq!(nearest_in_format(1000.0, FloatFormat::FP8_E4M3), (14, 5, 1));
+/// ```
+pub fn nearest_in_format(value: f64, fmt: FloatFormat) -> (u128, i32, i8) {
+    let (ns, ne) = (fmt.num_sig_bits, fmt.num_exp_bits);
+    assert!((2..=113).contains(&ns), "nearest_in_format: bad significand width {}", ns);
+    assert!((2..=20).contains(&ne), "nearest_in_format: bad exponent width {}", ne);
+
+    let adjust = mask!(ne - 1) + ns as i32 - 1;
+    let exp_min = -adjust;
+    let exp_top = mask!(ne) - adjust;
+    let (exp_max, sig_max) = if fmt.has_infinity {
+        (exp_top - 1, mask!(ns))
+    } else {
+        (exp_top, mask!(ns) - 1)
+    };
+    let overflow = |sign| if fmt.has_infinity { (0, exp_top, sign) } else { (sig_max, exp_max, sign) };
+
+    let (sigbits, exp, sign) = value.to_float_parts();
+    if exp == f64::EXP_INF_NAN {
+        return match (sigbits != 0, fmt.has_infinity) {
+            (true, true) => (1 << (ns - 2), exp_top, sign),
+            (true, false) => (mask!(ns - 1), exp_top, sign),
+            (false, _) => overflow(sign),
+        };
+    }
+    if sigbits == 0 {
+        return (0, exp_min, sign);
+    }
+
+    // Round at the quantum of the binade of the leading bit,
+    // which is never finer than that of the subnormals.
+    let (sig, exp) = (sigbits as u128, exp as i32);
+    let top = exp + (u128::BITS - sig.leading_zeros()) as i32 - 1;
+    let mut q = (top - (ns - 1) as i32).max(exp_min + 1);
+    let mut m = if exp < q {
+        shr_round_even(sig, (q - exp) as u32)
+    } else {
+        sig << (exp - q)
+    };
+    if m >> ns != 0 {
+        m >>= 1;
+        q += 1;
+    }
+
+    if m == 0 {
+        (0, exp_min, sign)
+    } else if m >> (ns - 1) == 0 {
+        (m << 1, exp_min, sign)
+    } else if q > exp_max || (q == exp_max && m > sig_max) {
+        overflow(sign)
+    } else {
+        (m, q, sign)
+    }
+}
+
+/// Convert parts of a `Src` float into the parts of the
+/// nearest `Dst` float, rounding to nearest with ties to
+/// even. Values out of range for `Dst` become an infinity, a
+/// subnormal or a zero.
+///
+/// NaN payloads keep their high bits, with the quiet bit set
+/// if narrowing would otherwise lose the payload entirely.
+///
+/// # Examples
+/// ```
+/// # use float_parts::{cast_parts, ToFloatParts};
+/// let parts = f64::decompose_array([0.1])[0];
+/// let narrow = cast_parts::<f64, f32>(parts);
+/// assert_eq!(f32::from(narrow), 0.1f64 as f32);
+/// let wide = cast_parts::<f32, f64>(narrow);
+/// assert_eq!(f64::from(wide), 0.1f32 as f64);
+/// ```
+pub fn cast_parts<Src, Dst>(
+    parts: FloatParts<Src::SigBits, Src::Exp>,
+) -> FloatParts<Dst::SigBits, Dst::Exp>
+where
+    Src: ToFloatParts,
+    Dst: ToFloatParts,
+    Src::SigBits: Into<u128>,
+    Src::Exp: Into<i32>,
+    Dst::SigBits: TryFrom<u128>,
+{
+    let FloatParts { sigbits, exp, sign } = parts;
+    let (sigbits, exp): (u128, i32) = (sigbits.into(), exp.into());
+
+    let x = if exp == Src::EXP_INF_NAN.into() {
+        let (ns, nd) = (Src::NUM_SIG_BITS, Dst::NUM_SIG_BITS);
+        let mut payload = if nd >= ns {
+            sigbits << (nd - ns)
+        } else {
+            sigbits >> (ns - nd)
+        };
+        if sigbits != 0 && payload == 0 {
+            payload = 1 << (nd - 2);
+        }
+        let Ok(payload) = Dst::SigBits::try_from(payload) else {
+            unreachable!("cast_parts: payload wider than significand");
+        };
+        Dst::from_float_parts(payload, Dst::EXP_INF_NAN, sign)
+    } else {
+        Dst::from_normalized_significand(sigbits, exp, sign)
+    };
+
+    let (sigbits, exp, sign) = x.to_float_parts();
+    FloatParts { sigbits, exp, sign }
+}
+
+/// True iff `self_f32` and `other` are the same real number,
+/// comparing parts rather than widening with `as`. NaNs are
+/// never equal to anything, zeros of either sign are equal,
+/// and infinities are equal if their signs match.
+///
+/// # Examples
+/// ```
+/// # use float_parts::same_value_as_f64;
+/// assert!(same_value_as_f64(0.5, 0.5));
+/// assert!(same_value_as_f64(0.1, 0.1f32 as f64));
+/// assert!(!same_value_as_f64(0.1, 0.1));
+/// assert!(!same_value_as_f64(f32::NAN, f64::NAN));
+/// ```
+pub fn same_value_as_f64(self_f32: f32, other: f64) -> bool {
+    let (sa, ea, signa) = self_f32.to_float_parts();
+    let (sb, eb, signb) = other.to_float_parts();
+    if ea == f32::EXP_INF_NAN || eb == f64::EXP_INF_NAN {
+        return ea == f32::EXP_INF_NAN && eb == f64::EXP_INF_NAN && sa == 0 && sb == 0 && signa == signb;
+    }
+    if sa == 0 && sb == 0 {
+        return true;
+    }
+    signa == signb && same_value(sa as u128, ea as i32, sb as u128, eb as i32)
+}
+
+/// The mean unbiased binary exponent, as returned by
+/// [ToFloatParts::to_scientific_parts], of the finite nonzero
+/// elements of `values`: the center of their dynamic range
+/// on a log scale. Subnormals are normalized. Returns `None`
+/// if there are no finite nonzero elements.
+///
+/// # Examples
+/// ```
+/// # use float_parts::mean_exponent;
+/// assert_eq!(mean_exponent(&[0.25f32, 1.0, 3.0, 16.0, 0.0, f32::NAN]), Some(0.75));
+/// assert_eq!(mean_exponent::<f64>(&[]), None);
+/// ```
+pub fn mean_exponent<T>(values: &[T]) -> Option<f64>
+where
+    T: ToFloatParts,
+    T::Exp: Into<f64>,
+{
+    let mut total = 0.0;
+    let mut count = 0;
+    for &x in values {
+        let (mantissa, exp, _) = x.to_scientific_parts();
+        if mantissa != 0.0 && mantissa.is_finite() {
+            total += exp.into();
+            count += 1;
+        }
+    }
+    (count > 0).then(|| total / count as f64)
+}
+
+/// The signed distance in ULPs from `reference` to
+/// `computed`, as counted by [ToFloatParts::ulps_between]:
+/// positive when `computed` is too large, negative when it is
+/// too small. Gives NaN if either argument is a NaN.
+///
+/// # Examples
+/// ```
+/// # use float_parts::ulp_error;
+/// let reference = 1.0f32;
+/// assert_eq!(ulp_error(f32::from_bits(reference.to_bits() + 3), reference), 3.0);
+/// assert_eq!(ulp_error(reference.next_down(), reference), -1.0);
+/// ```
+pub fn ulp_error<T: ToFloatParts>(computed: T, reference: T) -> f64 {
+    let Some(ulps) = computed.ulps_between(reference) else {
+        return f64::NAN;
+    };
+    match computed.try_cmp(reference) {
+        Ok(std::cmp::Ordering::Less) => -(ulps as f64),
+        _ => ulps as f64,
+    }
+}
+
+/// The smallest unsigned fixed-point format `(integer_bits,
+/// fractional_bits)` holding the magnitudes of all `values`
+/// exactly: enough integer bits for the largest and, from
+/// [ToFloatParts::fractional_bit_count], enough fractional
+/// bits for the finest. A sign bit, if wanted, is extra.
+/// Returns `None` if `values` is empty or holds an infinity
+/// or NaN.
+///
+/// # Examples
+/// ```
+/// # use float_parts::suggest_fixed_point_format;
+/// assert_eq!(suggest_fixed_point_format(&[0.25f32, -100.0]), Some((7, 2)));
+/// assert_eq!(suggest_fixed_point_format(&[0.0f32]), Some((0, 0)));
+/// ```
+pub fn suggest_fixed_point_format<T>(values: &[T]) -> Option<(u32, u32)>
+where
+    T: ToFloatParts,
+    T::Exp: Into<i32>,
+{
+    if values.is_empty() {
+        return None;
+    }
+    let (mut integer_bits, mut fractional_bits) = (0, 0);
+    for &x in values {
+        if !x.is_finite_parts() {
+            return None;
+        }
+        let (mantissa, exp, _) = x.to_scientific_parts();
+        if mantissa != 0.0 {
+            integer_bits = integer_bits.max(exp.into() + 1);
+        }
+        fractional_bits = fractional_bits.max(x.fractional_bit_count());
+    }
+    Some((integer_bits as u32, fractional_bits))
+}
+
+/// The smallest and largest non-NaN elements of `values`,
+/// found in one pass by comparing
+/// [ToFloatParts::total_order_key]s. NaNs are skipped, and
+/// `-0.0` orders below `0.0`. Returns `None` if there are no
+/// non-NaN elements.
+///
+/// # Examples
+/// ```
+/// # use float_parts::min_max;
+/// assert_eq!(min_max(&[2.0f32, f32::NAN, -1.0, 0.5]), Some((-1.0, 2.0)));
+/// assert_eq!(min_max::<f32>(&[f32::NAN]), None);
+/// ```
+pub fn min_max<T>(values: &[T]) -> Option<(T, T)>
+where
+    T: ToFloatParts,
+    T::SigBits: Ord + Into<u128>,
+{
+    let mut values = values.iter().copied().filter(|&x| !is_nan(x));
+    let first = values.next()?;
+    let (mut lo, mut lo_key) = (first, first.total_order_key());
+    let (mut hi, mut hi_key) = (first, first.total_order_key());
+    for x in values {
+        let key = x.total_order_key();
+        if key < lo_key {
+            (lo, lo_key) = (x, key);
+        } else if key > hi_key {
+            (hi, hi_key) = (x, key);
+        }
+    }
+    Some((lo, hi))
+}
+
+/// The floats of a slice, split by
+/// [partition_by_category].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryPartition<T> {
+    /// Finite values, including zeros and subnormals.
+    pub finite: Vec<T>,
+    /// Infinities of either sign.
+    pub infinite: Vec<T>,
+    /// NaNs.
+    pub nan: Vec<T>,
+}
+
+/// Split `values` into finite values, infinities and NaNs in
+/// one pass, classifying by their parts. Each group keeps the
+/// order of the input.
+///
+/// # Examples
+/// ```
+/// # use float_parts::partition_by_category;
+/// let p = partition_by_category(&[1.0f32, f32::NAN, -0.0, f32::NEG_INFINITY]);
+/// assert_eq!(p.finite, [1.0, -0.0]);
+/// assert_eq!(p.infinite, [f32::NEG_INFINITY]);
+/// assert_eq!(p.nan.len(), 1);
+/// ```
+pub fn partition_by_category<T>(values: &[T]) -> CategoryPartition<T>
+where
+    T: ToFloatParts,
+    T::SigBits: Into<u128>,
+{
+    let mut partition = CategoryPartition {
+        finite: Vec::new(),
+        infinite: Vec::new(),
+        nan: Vec::new(),
+    };
+    for &x in values {
+        let (sigbits, _, _) = x.to_float_parts();
+        let group = if x.is_finite_parts() {
+            &mut partition.finite
+        } else if sigbits.into() == 0 {
+            &mut partition.infinite
+        } else {
+            &mut partition.nan
+        };
+        group.push(x);
+    }
+    partition
+}
+
+/// Analysis of an `f64` against `f32` precision.
+pub trait NarrowToF32 {
+    /// The `f32` nearest to `self` (rounding to nearest with
+    /// ties to even, as `as f32` does), returned as an `f64`,
+    /// along with how the rounded value compares to `self`:
+    /// [Ordering::Greater] if it rounded up,
+    /// [Ordering::Less] if it rounded down, and
+    /// [Ordering::Equal] if `self` is exactly representable.
+    ///
+    /// Values beyond the `f32` range round to an infinity.
+    /// NaNs give a NaN and [Ordering::Equal].
+    ///
+    /// [Ordering::Greater]: std::cmp::Ordering::Greater
+    /// [Ordering::Less]: std::cmp::Ordering::Less
+    /// [Ordering::Equal]: std::cmp::Ordering::Equal
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::NarrowToF32;
+    /// # use std::cmp::Ordering;
+    /// assert_eq!(0.5f64.round_to_f32_precision(), (0.5, Ordering::Equal));
+    /// assert_eq!(0.1f64.round_to_f32_precision(), (0.1f32 as f64, Ordering::Greater));
+    /// ```
+    fn round_to_f32_precision(self) -> (f64, std::cmp::Ordering);
+
+    /// The largest `f32` not greater than `self` and the
+    /// smallest `f32` not less than `self`. These are equal
+    /// when `self` is exactly representable. Beyond the `f32`
+    /// range the outer neighbor is an infinity: for example,
+    /// `1e300` gives `(f32::MAX, f32::INFINITY)`. NaNs give a
+    /// pair of NaNs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::NarrowToF32;
+    /// assert_eq!(0.1f64.bracketing_f32(), (0.1f32.next_down(), 0.1f32));
+    /// assert_eq!((-0.5f64).bracketing_f32(), (-0.5, -0.5));
+    /// ```
+    fn bracketing_f32(self) -> (f32, f32);
+}
+
+impl NarrowToF32 for f64 {
+    fn round_to_f32_precision(self) -> (f64, std::cmp::Ordering) {
+        let (sigbits, exp, sign) = self.to_float_parts();
+        let narrow = cast_parts::<f64, f32>(FloatParts { sigbits, exp, sign });
+        let rounded = f64::from(cast_parts::<f32, f64>(narrow));
+        let order = rounded.partial_cmp(&self).unwrap_or(std::cmp::Ordering::Equal);
+        (rounded, order)
+    }
+
+    fn bracketing_f32(self) -> (f32, f32) {
+        let (rounded, order) = self.round_to_f32_precision();
+        let rounded = rounded as f32;
+        match order {
+            std::cmp::Ordering::Less => (rounded, rounded.next_up()),
+            std::cmp::Ordering::Equal => (rounded, rounded),
+            std::cmp::Ordering::Greater => (rounded.next_down(), rounded),
+        }
+    }
+}
+
+/// A float whose arithmetic operators work in the parts
+/// domain: operands are decomposed, their significands and
+/// exponents are combined exactly, and the result is
+/// recomposed with [ToFloatParts::from_normalized_significand],
+/// rounding to nearest with ties to even. Results are
+/// bit-for-bit those of native arithmetic; the point is that
+/// each step can be followed.
+///
+/// Operations involving infinities or NaNs fall back to
+/// native arithmetic.
+///
+/// # Examples
+/// ```
+/// # use float_parts::PartsFloat;
+/// let x = PartsFloat(0.1f64) + PartsFloat(0.2);
+/// assert_eq!(x.0, 0.1 + 0.2);
+/// assert_eq!((PartsFloat(1.5f32) * PartsFloat(-3.0)).0, -4.5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartsFloat<T>(pub T);
+
+impl<T: ToFloatParts> std::ops::Add for PartsFloat<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        PartsFloat(self.0.checked_add_exact(other.0).0)
+    }
+}
+
+impl<T: ToFloatParts> std::ops::Sub for PartsFloat<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T: ToFloatParts> std::ops::Mul for PartsFloat<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        PartsFloat(self.0.checked_mul_exact(other.0).0)
+    }
+}
+
+impl<T: ToFloatParts> std::ops::Neg for PartsFloat<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        let (_, _, sign) = self.0.to_float_parts();
+        PartsFloat(self.0.with_sign(-sign))
+    }
+}
+
+/// Iterator adaptor yielding the
+/// [ToFloatParts::to_float_parts] of each float from the
+/// underlying iterator. Built by
+/// [FloatPartsIterExt::float_parts].
+#[derive(Debug, Clone)]
+pub struct FloatPartsIter<I> {
+    iter: I,
+}
+
+impl<I> Iterator for FloatPartsIter<I>
+where
+    I: Iterator,
+    I::Item: ToFloatParts,
+{
+    type Item = (
+        <I::Item as ToFloatParts>::SigBits,
+        <I::Item as ToFloatParts>::Exp,
+        i8,
+    );
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(ToFloatParts::to_float_parts)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Extension trait for decomposing the floats of an
+/// iterator without writing a closure.
+pub trait FloatPartsIterExt: Iterator + Sized {
+    /// Adapt this iterator to yield the parts of its floats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use float_parts::FloatPartsIterExt;
+    /// let floats = vec![1.0f32, -2.0f32];
+    /// let parts: Vec<_> = floats.iter().copied().float_parts().collect();
+    /// assert_eq!(parts, vec![(1 << 23, -23, 1), (1 << 23, -22, -1)]);
+    /// ```
+    fn float_parts(self) -> FloatPartsIter<Self> {
+        FloatPartsIter { iter: self }
+    }
+}
+
+impl<I> FloatPartsIterExt for I
+where
+    I: Iterator,
+    I::Item: ToFloatParts,
+{
+}
+
+#[test]
+fn test_to_float_parts_f64() {
+    assert_eq!((1.0f64).to_float_parts(), (1 << 52, -52, 1));
+    let denorm = -f64::powf(2.0, -1023.0 - 2.0);
+    assert_eq!(denorm.to_float_parts(), (1 << 50, f64::EXP_MIN, -1));
+    assert_eq!(f64::NAN.to_float_parts().1, f64::EXP_INF_NAN);
+}
+
+#[test]
+fn test_to_latex_f64() {
+    assert_eq!((-6.0f64).to_latex(), r"(-1)^{1} \times 1.1_2 \times 2^{2}");
+    assert_eq!(0.0f64.to_latex(), r"(-1)^{0} \times 0.0_2 \times 2^{-1022}");
+    assert_eq!(f64::NAN.to_latex(), r"\mathrm{NaN}");
+}
+
+#[test]
+fn test_from_float_parts_f64() {
+    for x in [1.0f64, -3.5, f64::MIN_POSITIVE, -f64::powf(2.0, -1060.0), 0.0] {
+        let (sigbits, exp, sign) = x.to_float_parts();
+        assert_eq!(f64::from_float_parts(sigbits, exp, sign).to_bits(), x.to_bits());
+    }
+    assert_eq!(f64::from_float_parts_saturating(1 << 52, i16::MAX, 1), f64::INFINITY);
+    assert_eq!(f64::from_float_parts_saturating(3 << 51, f64::EXP_MAX, 1), 1.5 * 2.0f64.powi(1023));
+    // Zero sigbits stay zero whichever way the exponent is out
+    // of range.
+    assert_eq!(f64::from_float_parts_saturating(0, i16::MAX, 1).to_bits(), 0.0f64.to_bits());
+    assert_eq!(f64::from_float_parts_saturating(0, i16::MAX, -1).to_bits(), (-0.0f64).to_bits());
+    assert_eq!(f64::from_float_parts_saturating(0, i16::MIN, -1).to_bits(), (-0.0f64).to_bits());
+}
+
+#[test]
+fn test_from_normalized_significand_f64() {
+    // Left shift needed.
+    assert_eq!(f64::from_normalized_significand(5, 10, 1), 5120.0);
+    // Right shift needed, rounding up.
+    let sig = (1u128 << 100) + (3 << 46);
+    let expected = 1.0 + f64::EPSILON;
+    assert_eq!(f64::from_normalized_significand(sig, -100, 1), expected);
+    // Rounding carries into the next binade.
+    let sig = (1u128 << 54) - 1;
+    assert_eq!(f64::from_normalized_significand(sig, -54, 1), 1.0);
+    // Rounding up out of the subnormal range.
+    let sig = (1u128 << 53) - 1;
+    assert_eq!(f64::from_normalized_significand(sig, -1075, 1), f64::MIN_POSITIVE);
+    assert_eq!(f64::from_normalized_significand(1, -1076, -1).to_bits(), (-0.0f64).to_bits());
+    assert_eq!(f64::from_normalized_significand(u128::MAX, 900, -1), f64::NEG_INFINITY);
+}
+
+#[test]
+fn test_to_fixed_point_f64() {
+    assert_eq!((-0.75f64).to_fixed_point(2), Some(-3));
+    assert_eq!(f64::powf(2.0, -60.0).to_fixed_point(8), Some(0));
+    assert_eq!((-f64::powf(2.0, 63.0)).to_fixed_point(0), Some(i64::MIN));
+    assert_eq!(f64::powf(2.0, 63.0).to_fixed_point(0), None);
+    assert_eq!(1.0f64.to_fixed_point(u32::MAX), None);
+    assert_eq!(0.0f64.to_fixed_point(u32::MAX), Some(0));
+}
+
+#[test]
+fn test_from_fixed_point_f64() {
+    assert_eq!(f64::from_fixed_point(0, 8), 0.0);
+    assert_eq!(f64::from_fixed_point(i64::MAX, 0), 9223372036854775807.0);
+    assert_eq!(f64::from_fixed_point(1, 1100), 0.0);
+    assert_eq!(f64::from_fixed_point(3, 1074), f64::from_bits(3));
+    for i in -1000..1000 {
+        let x = i as f64 * 0.375;
+        let q = x.to_fixed_point(12).unwrap();
+        assert_eq!(f64::from_fixed_point(q, 12), x);
+    }
+}
+
+#[test]
+fn test_split_scale_f64() {
+    for x in [1.0f64, 0.1, -1.0e300, f64::MAX, f64::MIN_POSITIVE, f64::from_bits(1), -f64::from_bits(12345)] {
+        let (m, s) = x.split_scale();
+        assert!((1.0..2.0).contains(&m.abs()));
+        assert_eq!(m * s, x);
+    }
+    assert_eq!((-0.0f64).split_scale().0.to_bits(), (-0.0f64).to_bits());
+}
+
+#[test]
+fn test_midpoint_parts_f64() {
+    let pairs = [
+        (1.0f64, 1.0e-300),
+        (-1.0e300, 3.0e299),
+        (0.1, 0.2),
+        (f64::MIN_POSITIVE, -f64::from_bits(4)),
+        (1.0, -1.0),
+        (1.0 + f64::EPSILON, -1.0e-30),
+    ];
+    for (a, b) in pairs {
+        // Halving is exact away from the subnormal range.
+        let expected = (a / 2.0) + (b / 2.0);
+        assert_eq!(a.midpoint_parts(b), expected, "{a} {b}");
+        assert_eq!(b.midpoint_parts(a), expected, "{a} {b}");
+    }
+    assert_eq!(f64::MIN.midpoint_parts(f64::MIN), f64::MIN);
+    assert_eq!(f64::from_bits(3).midpoint_parts(0.0), f64::from_bits(2));
+    assert!(f64::INFINITY.midpoint_parts(f64::NEG_INFINITY).is_nan());
+}
+
+#[test]
+fn test_from_sign_exp_fraction_f64() {
+    assert_eq!(f64::from_sign_exp_fraction(1, 1024, 1.5), f64::INFINITY);
+    assert_eq!(f64::from_sign_exp_fraction(-1, -1074, 1.0), -f64::from_bits(1));
+    for x in [0.1f64, -7.0e-310, 12345.678] {
+        let (m, s) = x.split_scale();
+        let exp = s.log2() as i16;
+        assert_eq!(f64::from_sign_exp_fraction(m.signum() as i8, exp, m), x);
+    }
+}
+
+#[test]
+fn test_decompose_array_f32() {
+    let arr = [1.5f32, -0.0, f32::from_bits(7), f32::NEG_INFINITY];
+    let parts = f32::decompose_array(arr);
+    for (x, p) in arr.into_iter().zip(parts) {
+        assert_eq!((p.sigbits, p.exp, p.sign), x.to_float_parts());
+    }
+}
+
+#[test]
+fn test_total_order_key_f64() {
+    let mut xs = vec![
+        f64::NEG_INFINITY, -1.0e300, -1.0, -f64::MIN_POSITIVE, -f64::from_bits(1), -0.0,
+        0.0, f64::from_bits(1), f64::MIN_POSITIVE, 1.0, 1.0e300, f64::INFINITY,
+    ];
+    for a in &xs {
+        for b in &xs {
+            assert!(f64::verify_ordering_invariant(*a, *b));
+            assert_eq!(a.total_order_key().cmp(&b.total_order_key()), a.total_cmp(b));
+        }
+    }
+    let sorted = xs.clone();
+    xs.reverse();
+    xs.sort_by_key(|x| x.total_order_key());
+    assert_eq!(xs, sorted);
+}
+
+#[test]
+fn test_to_scientific_parts_f64() {
+    assert_eq!(1.0f64.to_scientific_parts(), (1.0, 0, 1));
+    assert_eq!(f64::MAX.to_scientific_parts(), (2.0 - f64::EPSILON, 1023, 1));
+    assert_eq!((-f64::from_bits(3)).to_scientific_parts(), (1.5, -1073, -1));
+    assert_eq!((-0.0f64).to_scientific_parts(), (0.0, 0, -1));
+    let (m, e, _) = f64::NAN.to_scientific_parts();
+    assert!(m.is_nan());
+    assert_eq!(e, f64::EXP_INF_NAN);
+}
+
+#[test]
+fn test_is_integer_f64() {
+    assert!((-0.0f64).is_integer());
+    assert!(f64::MAX.is_integer());
+    assert!(9007199254740991.0f64.is_integer());
+    assert!(!4503599627370495.5f64.is_integer());
+    assert!(!f64::from_bits(1).is_integer());
+    assert!(!f64::NEG_INFINITY.is_integer());
+}
+
+#[test]
+fn test_from_float_parts_slice_f64() {
+    let xs = [0.1f64, -0.0, f64::from_bits(5), f64::MAX, f64::NEG_INFINITY, f64::NAN];
+    let mut signs = Vec::new();
+    let mut exps = Vec::new();
+    let mut sigbits = Vec::new();
+    for (s, e, sign) in xs.iter().copied().float_parts() {
+        sigbits.push(s);
+        exps.push(e);
+        signs.push(sign);
+    }
+    let mut out = [0.0; 6];
+    f64::from_float_parts_slice(&signs, &exps, &sigbits, &mut out);
+    for (x, y) in xs.iter().zip(out) {
+        assert_eq!(x.to_bits(), y.to_bits());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_float_parts_slice_mismatch() {
+    let mut out = [0.0f64; 2];
+    f64::from_float_parts_slice(&[1, 1], &[0], &[0, 0], &mut out);
+}
+
+#[test]
+fn test_relative_ulp_f64() {
+    for x in [1.0f64, 1.5, -1.0e300, f64::MIN_POSITIVE, 3.0e-200] {
+        let r = x.relative_ulp();
+        assert!(r <= f64::EPSILON && r > f64::EPSILON / 2.0);
+    }
+    assert!(f64::from_bits(1000).relative_ulp() > f64::EPSILON);
+    assert_eq!(0.0f64.relative_ulp(), f64::INFINITY);
+    assert!(f64::INFINITY.relative_ulp().is_nan());
+}
+
+#[test]
+fn test_fractional_bit_count_f64() {
+    assert_eq!((1.0f64 + f64::EPSILON).fractional_bit_count(), 52);
+    assert_eq!((-0.75f64).fractional_bit_count(), 2);
+    assert_eq!(1.0e300f64.fractional_bit_count(), 0);
+    assert_eq!(f64::from_bits(1).fractional_bit_count(), 1074);
+    assert_eq!(f64::NAN.fractional_bit_count(), 0);
+}
+
+#[test]
+fn test_from_float_parts_struct() {
+    for x in [1.5f32, -0.0, f32::from_bits(9), f32::MAX, f32::NEG_INFINITY] {
+        let y: f32 = f32::decompose_array([x])[0].into();
+        assert_eq!(x.to_bits(), y.to_bits());
+    }
+    for x in [0.1f64, -f64::from_bits(3), f64::MIN] {
+        let y: f64 = f64::decompose_array([x])[0].into();
+        assert_eq!(x.to_bits(), y.to_bits());
+    }
+    let nan: f64 = FloatParts { sigbits: 5, exp: f64::EXP_INF_NAN, sign: 1 }.into();
+    assert_eq!(nan.to_bits(), f64::INFINITY.to_bits() | 5);
+    let big: f64 = FloatParts { sigbits: 1 << 52, exp: 5000, sign: -1 }.into();
+    assert_eq!(big, f64::NEG_INFINITY);
+    let zero: f64 = FloatParts { sigbits: 0, exp: 5000, sign: -1 }.into();
+    assert_eq!(zero.to_bits(), (-0.0f64).to_bits());
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_float_parts() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let bytes: Vec<u8> = (0..4096u32).map(|i| (i * 131 % 251) as u8).collect();
+    let mut u = Unstructured::new(&bytes);
+    while !u.is_empty() {
+        let parts = FloatParts::<u32, i16>::arbitrary(&mut u).unwrap();
+        assert_eq!(parts.validate::<f32>(), Ok(()), "{:?}", parts);
+        let parts = FloatParts::<u64, i16>::arbitrary(&mut u).unwrap();
+        assert_eq!(parts.validate::<f64>(), Ok(()), "{:?}", parts);
+    }
+}
+
+#[test]
+fn test_with_sign_f64() {
+    for x in [1.0f64, f64::from_bits(7), f64::MAX, 0.0, f64::INFINITY, f64::from_bits(0x7ff0_0000_0000_0001)] {
+        assert_eq!(x.with_sign(-1).to_bits(), (-x).to_bits());
+        assert_eq!((-x).with_sign(1).to_bits(), x.to_bits());
+        assert_eq!(x.with_sign(1).to_bits(), x.to_bits());
+    }
+}
+
+// A mock format laid out like `f32`, but with the exponent
+// adjustment of the original trait default, which disagrees
+// with the range constants copied from `f32`.
+#[cfg(all(test, debug_assertions))]
+trait BrokenParts: Copy {
+    const NUM_SIG_BITS: u32;
+    const NUM_EXP_BITS: u32;
+    const EXP_INF_NAN: i16;
+    const EXP_MIN: i16;
+    const EXP_ADJUST: i16;
+    type SigBits;
+    type Exp;
+    fn to_float_parts(self) -> (Self::SigBits, Self::Exp, i8);
+    fn from_float_parts(sigbits: Self::SigBits, exp: Self::Exp, sign: i8) -> Self;
+}
+
+#[cfg(all(test, debug_assertions))]
+#[derive(Clone, Copy)]
+struct BrokenF32(f32);
+
+#[cfg(all(test, debug_assertions))]
+impl BrokenF32 {
+    fn to_bits(self) -> u32 {
+        self.0.to_bits()
+    }
+
+    fn from_bits(bits: u32) -> Self {
+        BrokenF32(f32::from_bits(bits))
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+impl BrokenParts for BrokenF32 {
+    const NUM_SIG_BITS: u32 = f32::NUM_SIG_BITS;
+    const NUM_EXP_BITS: u32 = f32::NUM_EXP_BITS;
+    const EXP_INF_NAN: i16 = f32::EXP_INF_NAN;
+    const EXP_MIN: i16 = f32::EXP_MIN;
+    const EXP_ADJUST: i16 =
+        mask!(Self::NUM_EXP_BITS - 1) as i16 - Self::NUM_SIG_BITS as i16 + 1;
+    type SigBits = u32;
+    type Exp = i16;
+
+    decompose_parts!{u32, i16}
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "outside")]
+fn test_to_float_parts_broken_impl() {
+    // The biased exponent of f32::MAX, adjusted by too little,
+    // lands above EXP_INF_NAN.
+    BrokenF32(f32::MAX).to_float_parts();
+}
+
+#[test]
+#[should_panic(expected = "wider than")]
+fn test_check_float_parts_broken() {
+    // The parts of 1.0f32 as decomposed by an impl that puts
+    // the implicit bit one place too high.
+    let f = f32::EXP_MIN as i32..=f32::EXP_INF_NAN as i32;
+    let bits = 1.0f32.to_bits() as u128;
+    check_float_parts((1 << 24, -23, 1), 24, f, (bits, bits));
+}
+
+#[test]
+#[should_panic(expected = "do not reconstruct")]
+fn test_check_float_parts_bad_adjust() {
+    // The parts of 1.0f32 as decomposed with the wrong
+    // exponent adjustment, which then fail to round-trip.
+    let (sigbits, exp, sign) = (1 << 23, 23, 1);
+    let rebuilt = f32::from_float_parts(sigbits, exp, sign).to_bits() as u128;
+    let f = f32::EXP_MIN as i32..=f32::EXP_INF_NAN as i32;
+    check_float_parts((sigbits as u128, exp as i32, sign), 24, f, (1.0f32.to_bits() as u128, rebuilt));
+}
+
+#[test]
+fn test_exponent_as_float_f64() {
+    assert_eq!(f64::MAX.exponent_as_float(), f64::powi(2.0, 1023));
+    assert_eq!(f64::MIN_POSITIVE.exponent_as_float(), f64::MIN_POSITIVE);
+    assert_eq!(f64::from_bits(1).exponent_as_float(), f64::from_bits(1));
+    assert_eq!(f64::NEG_INFINITY.exponent_as_float(), f64::INFINITY);
+    assert!(f64::NAN.exponent_as_float().is_nan());
+}
+
+#[test]
+fn test_to_float_parts_raw_f64() {
+    let denorm = f64::from_bits(0x000f_0000_0000_0001);
+    assert_eq!(denorm.to_float_parts_raw(), (0xf_0000_0000_0001, 0, 1));
+    assert_eq!(denorm.to_float_parts(), (0x1e_0000_0000_0002, f64::EXP_MIN, 1));
+    assert_eq!((-2.0f64).to_float_parts_raw(), (0, 1024, -1));
+    assert_eq!(f64::NAN.to_float_parts_raw().1, 2047);
+}
+
+#[test]
+fn test_cast_parts() {
+    let xs = [1.0f64, -1.0e-40, 3.0e38, 1.0e39, f64::MIN_POSITIVE, -0.0, f64::INFINITY, 1.0 / 3.0];
+    for x in xs {
+        let parts = f64::decompose_array([x])[0];
+        let narrow = f32::from(cast_parts::<f64, f32>(parts));
+        assert_eq!(narrow.to_bits(), (x as f32).to_bits(), "{x}");
+        let back = f64::from(cast_parts::<f32, f64>(f32::decompose_array([narrow])[0]));
+        assert_eq!(back.to_bits(), (narrow as f64).to_bits(), "{x}");
+    }
+    let nan = f64::from_bits(0x7ff0_0000_0000_0001);
+    let parts = cast_parts::<f64, f32>(f64::decompose_array([nan])[0]);
+    assert!(f32::from(parts).is_nan());
+}
+
+#[test]
+fn test_checked_exact_f64() {
+    let xs = [1.0f64, 0.1, -3.0, 1.0e-300, 1.0e300, f64::from_bits(3), -0.0, 2.0f64.powi(60)];
+    for a in xs {
+        for b in xs {
+            let (sum, exact) = a.checked_add_exact(b);
+            assert_eq!(sum.to_bits(), (a + b).to_bits(), "{a} + {b}");
+            // The sum is exact iff the Knuth two-sum error is zero.
+            let bb = sum - a;
+            let err = (a - (sum - bb)) + (b - bb);
+            if sum.is_finite() {
+                assert_eq!(exact, err == 0.0, "{a} + {b}");
+            }
+            let (prod, exact) = a.checked_mul_exact(b);
+            assert_eq!(prod.to_bits(), (a * b).to_bits(), "{a} * {b}");
+            if prod.is_finite() && prod.abs() > 1.0e-290 {
+                assert_eq!(exact, a.mul_add(b, -prod) == 0.0, "{a} * {b}");
+            }
+        }
+    }
+    assert_eq!(1.0f64.checked_add_exact(1.0e-30), (1.0, false));
+    assert_eq!(f64::from_bits(3).checked_mul_exact(0.5), (f64::from_bits(2), false));
+}
+
+#[test]
+fn test_significand_bit_positions_f64() {
+    let bits: Vec<i16> = f64::from_bits(5).significand_bit_positions().collect();
+    assert_eq!(bits, vec![-1072, -1074]);
+    assert_eq!(f64::NAN.significand_bit_positions().count(), 0);
+    for x in [0.1f64, -7.25, 1.0e300] {
+        let sum: f64 = x.significand_bit_positions().map(|k| 2.0f64.powi(k as i32)).sum();
+        assert_eq!(sum, x.abs());
+    }
+}
+
+#[test]
+fn test_normalize_parts_f64() {
+    for x in [1.0f64, -0.1, f64::from_bits(6), f64::MAX] {
+        let p = f64::decompose_array([x])[0];
+        assert_eq!(f64::normalize_parts(p), p);
+        // Denormalize by moving bits across the exponent.
+        let tz = p.sigbits.trailing_zeros();
+        let q = FloatParts { sigbits: p.sigbits >> tz, exp: p.exp + tz as i16, sign: p.sign };
+        assert_eq!(f64::normalize_parts(q), p);
+    }
+    let p = FloatParts { sigbits: 1 << 52, exp: f64::EXP_MAX + 1, sign: 1 };
+    assert_eq!(f64::from(f64::normalize_parts(p)), f64::INFINITY);
+}
+
+#[test]
+fn test_max_parts_f64() {
+    assert_eq!(f64::MAX_PARTS, f64::MAX.to_float_parts());
+    let (sigbits, exp, sign) = f64::MAX_PARTS;
+    assert_eq!(f64::from_float_parts(sigbits, exp, -sign), f64::MIN);
+}
+
+#[test]
+fn test_two_sum() {
+    let xs = [1.0f32, -0.1, 3.0e7, 1.0e-30, -1.0e-45, 0.3, 16777215.0, -0.0];
+    for a in xs {
+        for b in xs {
+            let (s, e) = a.two_sum(b);
+            assert_eq!(s, a + b);
+            assert_eq!(s as f64 + e as f64, a as f64 + b as f64, "{a} + {b}");
+        }
+    }
+    let xs = [1.0f64, -0.1, 3.0e15, 1.0e-300, -f64::from_bits(3), 0.3, f64::MAX / 3.0];
+    for a in xs {
+        for b in xs {
+            // Compare against the branch-free Knuth two-sum.
+            let s = a + b;
+            let bb = s - a;
+            let expected = (a - (s - bb)) + (b - bb);
+            assert_eq!(a.two_sum(b), (s, expected), "{a} + {b}");
+        }
+    }
+    assert_eq!(f64::MAX.two_sum(f64::MAX), (f64::INFINITY, 0.0));
+    assert_eq!(1.0f64.two_sum(-1.0), (0.0, 0.0));
+}
+
+#[test]
+fn test_exponent_bucket_f64() {
+    let buckets: Vec<i32> = [0.125f64, 0.5, 1.0, 3.0, 1024.5].iter().map(|x| x.exponent_bucket()).collect();
+    assert_eq!(buckets, vec![-3, -1, 0, 1, 10]);
+    assert_eq!(f64::MIN_POSITIVE.exponent_bucket(), -1022);
+    assert_eq!(f64::MAX.exponent_bucket(), 1023);
+    assert_eq!((-0.0f64).exponent_bucket(), EXP_BUCKET_ZERO);
+    assert_eq!(f64::from_bits(1 << 51).exponent_bucket(), EXP_BUCKET_SUBNORMAL);
+    assert_eq!(f64::NEG_INFINITY.exponent_bucket(), EXP_BUCKET_INF);
+}
+
+#[test]
+fn test_from_hex_float_str_f64() {
+    let cases = [
+        ("0x1p0", 1.0f64),
+        ("0X1.921fb54442d18P+1", std::f64::consts::PI),
+        ("0x1.fffffffffffffp1023", f64::MAX),
+        ("0x1.fffffffffffff8p1023", f64::INFINITY),
+        ("0x0.0000000000001p-1022", f64::from_bits(1)),
+        ("0x0.00000000000008p-1022", 0.0),
+        ("0x0.00000000000008000000000000000000000000001p-1022", f64::from_bits(1)),
+        ("-0x10.8", -16.5),
+        ("+0x1.", 1.0),
+        ("0xffffffffffffffffffffffffffffffffffffffffp-160", 1.0),
+    ];
+    for (s, x) in cases {
+        assert_eq!(f64::from_hex_float_str(s), Ok(x), "{s}");
+    }
+    assert!(f64::from_hex_float_str("-nan").unwrap().is_nan());
+    assert_eq!(f64::from_hex_float_str("0x"), Err(ParseError::NoDigits));
+    assert_eq!(f64::from_hex_float_str("0x1p"), Err(ParseError::BadExponent));
+    assert_eq!(f64::from_hex_float_str("0x1.2.3"), Err(ParseError::Invalid));
+    for x in [0.1f64, -1.0e-310, 12345.678] {
+        let (m, e, _) = x.to_float_parts();
+        let s = format!("{}0x{:x}p{}", if x < 0.0 { "-" } else { "" }, m, e);
+        assert_eq!(f64::from_hex_float_str(&s), Ok(x), "{s}");
+    }
+}
+
+#[test]
+fn test_is_half_integer_f64() {
+    assert!(0.5f64.is_half_integer());
+    assert!(4503599627370495.5f64.is_half_integer());
+    assert!(!9007199254740990.0f64.is_half_integer());
+    assert!(!0.0f64.is_half_integer());
+    assert!(!f64::NAN.is_half_integer());
+}
+
+#[test]
+fn test_clamp_exponent_boundaries() {
+    assert_eq!(1.5f32.clamp_exponent(-200, -140), f32::from_bits(3 << 8));
+    let x = 1.0f32 + f32::EPSILON;
+    assert_eq!(x.clamp_exponent(-200, -148), f32::from_bits(2));
+    assert_eq!(x.clamp_exponent(-200, -151), 0.0);
+    assert_eq!(f32::from_bits(3).clamp_exponent(0, 10), 1.5);
+    assert_eq!(1.0f64.clamp_exponent(1024, 2000), f64::INFINITY);
+    assert!(f64::NAN.clamp_exponent(0, 1).is_nan());
+}
+
+#[test]
+fn test_mean_exponent() {
+    let xs: Vec<f64> = (-10..=20).map(|e| 1.5 * 2.0f64.powi(e)).collect();
+    assert_eq!(mean_exponent(&xs), Some(5.0));
+    assert_eq!(mean_exponent(&[f32::from_bits(1), f32::MAX]), Some(-11.0));
+    assert_eq!(mean_exponent(&[0.0f64, f64::INFINITY, f64::NAN]), None);
+}
+
+#[test]
+fn test_from_float_parts_nan_payload() {
+    let payloads = [1u64, 0x8_0000_0000_0000, 0xf_ffff_ffff_ffff, 0x1234_5678_9abc, 0x4_0000_dead_beef];
+    for payload in payloads {
+        for sign in [0u64, 1 << 63] {
+            let bits = sign | 0x7ff0_0000_0000_0000 | payload;
+            let (sigbits, exp, parts_sign) = f64::from_bits(bits).to_float_parts();
+            assert_eq!(exp, f64::EXP_INF_NAN);
+            assert_eq!(f64::from_float_parts(sigbits, exp, parts_sign).to_bits(), bits);
+            let narrow = payload as u32 & 0x7f_ffff;
+            if narrow != 0 {
+                let bits = ((sign >> 32) as u32) | 0x7f80_0000 | narrow;
+                let (sigbits, exp, parts_sign) = f32::from_bits(bits).to_float_parts();
+                assert_eq!(exp, f32::EXP_INF_NAN);
+                assert_eq!(f32::from_float_parts(sigbits, exp, parts_sign).to_bits(), bits);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reverse_significand_bits_f64() {
+    for x in [1.0f64, std::f64::consts::PI, -1.0e-300, 6.02e23, f64::from_bits(0x7ff0_0000_0bad_f00d)] {
+        let y = x.reverse_significand_bits();
+        assert_eq!(y.to_bits() >> 52, x.to_bits() >> 52);
+        assert_eq!(y.reverse_significand_bits().to_bits(), x.to_bits());
+    }
+    assert_eq!(1.5f64.reverse_significand_bits(), 1.0 + f64::EPSILON);
+}
+
+#[test]
+fn test_incr_significand_wrapping_f64() {
+    let top = f64::from_float_parts(mask!(53), -52, 1);
+    let wrapped = top.incr_significand_wrapping();
+    assert_eq!(wrapped, 1.0);
+    assert_eq!(wrapped.to_float_parts().1, top.to_float_parts().1);
+    assert_eq!(f64::MAX.incr_significand_wrapping(), f64::from_float_parts(1 << 52, f64::EXP_MAX, 1));
+    let denorm = f64::from_bits(mask!(52));
+    assert_eq!(denorm.incr_significand_wrapping().to_bits(), 0);
+    assert_eq!(2.0f64.incr_significand_wrapping(), 2.0f64.next_up());
+}
+
+#[test]
+fn test_float_parts_validate_f64() {
+    for x in [1.0f64, -0.0, f64::MIN_POSITIVE, f64::from_bits(6), f64::MAX, f64::NEG_INFINITY, f64::NAN] {
+        let (sigbits, exp, sign) = x.to_float_parts();
+        assert_eq!(FloatParts { sigbits, exp, sign }.validate::<f64>(), Ok(()), "{:e}", x);
+    }
+    let one = FloatParts { sigbits: 1u64 << 52, exp: -52i16, sign: -1 };
+    let cases = [
+        (FloatParts { sign: 2, ..one }, FloatPartsError::BadSign),
+        (FloatParts { sigbits: 1 << 53, ..one }, FloatPartsError::SigBitsTooWide),
+        (FloatParts { exp: f64::EXP_MIN - 1, ..one }, FloatPartsError::ExpOutOfRange),
+        (FloatParts { exp: f64::EXP_INF_NAN + 1, ..one }, FloatPartsError::ExpOutOfRange),
+        (FloatParts { sigbits: 3, ..one }, FloatPartsError::ImplicitBit),
+        (FloatParts { exp: f64::EXP_INF_NAN, ..one }, FloatPartsError::ImplicitBit),
+        (FloatParts { sigbits: 3, exp: f64::EXP_MIN, sign: 1 }, FloatPartsError::OddSubnormal),
+    ];
+    for (parts, err) in cases {
+        assert_eq!(parts.validate::<f64>(), Err(err), "{:?}", parts);
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_to_bigint_scaled_f64() {
+    use num_bigint::{BigInt, Sign};
+
+    let xs = [1.0f64, -0.1, f64::MAX, -f64::MIN_POSITIVE, f64::from_bits(1), 1.0e300, -7.0e-310];
+    for x in xs {
+        let (bigint, scale) = x.to_bigint_scaled().unwrap();
+        let sign = if bigint.sign() == Sign::Minus { -1 } else { 1 };
+        let magnitude = u128::try_from(bigint.magnitude()).unwrap();
+        let y = f64::from_normalized_significand(magnitude, scale, sign);
+        assert_eq!(y.to_bits(), x.to_bits(), "{:e}", x);
+    }
+    assert_eq!(0.0f64.to_bigint_scaled().unwrap().0, BigInt::ZERO);
+    assert_eq!(f64::NEG_INFINITY.to_bigint_scaled(), None);
+}
+
+#[test]
+fn test_binade_f64() {
+    for k in [-1022i16, -1, 0, 1, 52, 1023] {
+        let lo = f64::powi(2.0, k as i32);
+        assert_eq!(lo.binade(), Some(k));
+        assert_eq!((-lo).binade(), Some(k));
+        assert_eq!(lo.next_down().binade(), Some(if k == -1022 { f64::EXP_MIN } else { k - 1 }));
+        if k < 1023 {
+            assert_eq!((2.0 * lo).next_down().binade(), Some(k));
+        }
+    }
+    assert_eq!(f64::from_bits(1).binade(), Some(f64::EXP_MIN));
+    assert_eq!(f64::MAX.binade(), Some(1023));
+    assert_eq!(f64::INFINITY.binade(), None);
+    assert_eq!(f64::NAN.binade(), None);
+    assert_eq!((-0.0f64).binade(), None);
+}
+
+#[test]
+fn test_with_float_parts_f64() {
+    for x in [1.0f64, -0.3, f64::from_bits(5), f64::NEG_INFINITY, f64::NAN] {
+        let (sigbits, exp, sign) = x.to_float_parts();
+        let summary = |s: u64, e: i16, g: i8| (s.count_ones(), e as i32 * g as i32);
+        assert_eq!(x.with_float_parts(summary), summary(sigbits, exp, sign));
+        assert_eq!(x.with_float_parts(|s, e, g| (s, e, g)), (sigbits, exp, sign));
+    }
+}
+
+#[test]
+fn test_round_to_f32_precision() {
+    use std::cmp::Ordering;
+
+    let up = 1.0 + f64::powi(2.0, -24) + f64::powi(2.0, -40);
+    assert_eq!(up.round_to_f32_precision(), (1.0 + f64::powi(2.0, -23), Ordering::Greater));
+    let down = 1.0 + f64::powi(2.0, -25);
+    assert_eq!(down.round_to_f32_precision(), (1.0, Ordering::Less));
+    let exact = -3.0 * f64::powi(2.0, -140);
+    assert_eq!(exact.round_to_f32_precision(), (exact, Ordering::Equal));
+    assert_eq!(1.0e300f64.round_to_f32_precision(), (f64::INFINITY, Ordering::Greater));
+    for x in [0.1f64, -2.7e-40, 6.02e23, 1.0e-50] {
+        assert_eq!(x.round_to_f32_precision().0, x as f32 as f64);
+    }
+    assert!(f64::NAN.round_to_f32_precision().0.is_nan());
+}
+
+#[test]
+fn test_ulp_error_f64() {
+    let reference = 0.1f64;
+    let computed = f64::from_bits(reference.to_bits() + 3);
+    assert_eq!(ulp_error(computed, reference), 3.0);
+    assert_eq!(ulp_error(reference, computed), -3.0);
+    assert_eq!(ulp_error(-computed, -reference), -3.0);
+    assert_eq!(ulp_error(f64::from_bits(1), -f64::from_bits(1)), 2.0);
+    assert_eq!(ulp_error(reference, reference), 0.0);
+    assert!(ulp_error(f64::NAN, reference).is_nan());
+}
+
+#[test]
+fn test_from_raw_fields_f64() {
+    for x in [-f64::from_bits(0x000f_1234_5678_9abc), f64::from_bits(0x7ff4_0000_0000_beef), -1.0e100] {
+        let (sig_field, biased_exp, sign) = x.to_float_parts_raw();
+        let sign_bit = (sign < 0) as u32;
+        assert_eq!(f64::from_raw_fields(sign_bit, biased_exp as u32, sig_field).to_bits(), x.to_bits());
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_from_raw_fields_too_wide() {
+    f64::from_raw_fields(0, 1 << 11, 0);
+}
+
+#[test]
+fn test_partition_by_category_f64() {
+    let nan = f64::from_bits(0xfff0_0000_0000_0001);
+    let values = [f64::INFINITY, 2.0, f64::NAN, -0.0, f64::NEG_INFINITY, f64::from_bits(1), nan, -7.5];
+    let p = partition_by_category(&values);
+    assert_eq!(p.finite, [2.0, -0.0, f64::from_bits(1), -7.5]);
+    assert_eq!(p.infinite, [f64::INFINITY, f64::NEG_INFINITY]);
+    let nans: Vec<u64> = p.nan.iter().map(|x| x.to_bits()).collect();
+    assert_eq!(nans, [f64::NAN.to_bits(), nan.to_bits()]);
+    assert_eq!(partition_by_category::<f64>(&[]).finite, []);
+}
+
+#[test]
+fn test_epsilon() {
+    assert_eq!(<f32 as ToFloatParts>::EPSILON.to_bits(), f32::EPSILON.to_bits());
+    assert_eq!(<f64 as ToFloatParts>::EPSILON.to_bits(), f64::EPSILON.to_bits());
+}
+
+#[test]
+fn test_ulps_to_next_binade_f64() {
+    assert_eq!(1.0f64.ulps_to_next_binade(), Some(1 << 52));
+    assert_eq!((-1.5f64).ulps_to_next_binade(), Some(1 << 51));
+    assert_eq!(f64::MAX.ulps_to_next_binade(), Some(1));
+    assert_eq!(f64::MIN_POSITIVE.next_down().ulps_to_next_binade(), Some(1));
+    assert_eq!(f64::from_bits(1).ulps_to_next_binade(), Some((1 << 52) - 1));
+    assert_eq!(f64::NAN.ulps_to_next_binade(), None);
+    assert_eq!(f64::NEG_INFINITY.ulps_to_next_binade(), None);
+    let x = 3.7f64;
+    let n = x.ulps_to_next_binade().unwrap();
+    assert_eq!(x.ulps_between(4.0), Some(n));
+}
+
+#[test]
+fn test_implicit_bit_pos() {
+    assert_eq!(f32::IMPLICIT_BIT_POS, 23);
+    assert_eq!(f64::IMPLICIT_BIT_POS, 52);
+    for x in [1.0f64, -3.5e200, f64::MIN_POSITIVE] {
+        assert_eq!(x.to_float_parts().0 >> f64::IMPLICIT_BIT_POS, 1);
+    }
+}
+
+#[test]
+fn test_to_i64_saturating_f64() {
+    let xs = [
+        0.0f64, -0.0, 0.99, -3.9, 1.0e30, -1.0e30, 9.2e18, -9.3e18, 4503599627370497.0,
+        -(i64::MIN as f64), i64::MIN as f64, f64::from_bits(1), f64::INFINITY, f64::NEG_INFINITY,
+    ];
+    for x in xs {
+        assert_eq!(x.to_i64_saturating(), x as i64, "{:e}", x);
+    }
+    assert_eq!((-f64::NAN).to_i64_saturating(), 0);
+}
+
+#[test]
+fn test_bracketing_f32() {
+    let lo = 1.0f32;
+    let x = lo as f64 + f64::powi(2.0, -30);
+    assert_eq!(x.bracketing_f32(), (lo, lo.next_up()));
+    assert_eq!((-x).bracketing_f32(), (-lo.next_up(), -lo));
+    let tiny = f64::powi(2.0, -160);
+    assert_eq!(tiny.bracketing_f32(), (0.0, f32::from_bits(1)));
+    assert_eq!(1.0e300f64.bracketing_f32(), (f32::MAX, f32::INFINITY));
+    assert_eq!((-1.0e300f64).bracketing_f32(), (f32::NEG_INFINITY, f32::MIN));
+    assert_eq!(f64::INFINITY.bracketing_f32(), (f32::INFINITY, f32::INFINITY));
+    for x in [0.1f64, 3.0e38, -7.7e-42, 123456.789] {
+        let (a, b) = x.bracketing_f32();
+        assert!(a as f64 <= x && x <= b as f64 && a.next_up() == b, "{:e}", x);
+    }
+}
+
+#[test]
+fn test_round_trip_precision_f64() {
+    let xs = [0.1f64, -1.0 / 3.0, 1.0 + f64::powi(2.0, -24), 1.0 + 3.0 * f64::powi(2.0, -24), 6.02e23, -1.0e-30];
+    for x in xs {
+        assert_eq!(x.round_trip_precision(24), x as f32 as f64, "{:e}", x);
+    }
+    assert_eq!(f64::MAX.round_trip_precision(24), f64::INFINITY);
+    assert_eq!(f64::from_bits(7).round_trip_precision(2), f64::from_bits(8));
+    assert_eq!(f64::NAN.round_trip_precision(1).to_bits(), f64::NAN.to_bits());
+    assert_eq!(std::f64::consts::PI.round_trip_precision(53), std::f64::consts::PI);
+}
+
+#[test]
+fn test_radix_sort_key_f64() {
+    let mut xs = [
+        3.5f64, -0.0, f64::NAN, -1.0e-310, 0.0, f64::NEG_INFINITY, 1.0e-310, -2.0, f64::INFINITY, -f64::NAN,
+        f64::MAX, -7.25,
+    ];
+    for &x in &xs {
+        let key = x.to_radix_sort_key();
+        assert_eq!(f64::from_radix_sort_key(key).to_bits(), x.to_bits());
+        assert_eq!(key, x.total_order_key());
+    }
+    let mut keys: Vec<u64> = xs.iter().map(|x| x.to_radix_sort_key()).collect();
+    keys.sort();
+    xs.sort_by(f64::total_cmp);
+    let sorted: Vec<u64> = keys.into_iter().map(|k| f64::from_radix_sort_key(k).to_bits()).collect();
+    let reference: Vec<u64> = xs.iter().map(|x| x.to_bits()).collect();
+    assert_eq!(sorted, reference);
+}
+
+#[test]
+fn test_to_frexp_parts_f64() {
+    assert_eq!(1.0f64.to_frexp_parts(), (1 << 52, 1, 1));
+    assert_eq!(0.5f64.to_frexp_parts(), (1 << 52, 0, 1));
+    assert_eq!((-0.0f64).to_frexp_parts(), (0, 0, -1));
+    assert_eq!(f64::from_bits(1).to_frexp_parts(), (1 << 52, -1073, 1));
+    assert_eq!(f64::NAN.to_frexp_parts(), f64::NAN.to_float_parts());
+    for x in [0.1f64, -3.0e300, 7.0e-310, 0.75] {
+        let (sigbits, exp, sign) = x.to_frexp_parts();
+        let fraction = sigbits as f64 / f64::powi(2.0, 53);
+        assert!((0.5..1.0).contains(&fraction));
+        let y = f64::from_normalized_significand(sigbits as u128, exp as i32 - 53, sign);
+        assert_eq!(y, x);
+    }
+}
+
+#[test]
+fn test_to_float_parts_ftz_f64() {
+    assert_eq!(f64::from_bits(1).to_float_parts_ftz(), (0, f64::EXP_MIN, 1));
+    let denorm = -f64::MIN_POSITIVE.next_down();
+    assert_eq!(denorm.to_float_parts_ftz(), (-0.0f64).to_float_parts());
+    for x in [f64::MIN_POSITIVE, -1.0, 0.0, f64::INFINITY, f64::NAN] {
+        assert_eq!(x.to_float_parts_ftz(), x.to_float_parts());
+    }
+}
+
+#[test]
+fn test_count_representable_below_f64() {
+    assert_eq!(0.0f64.count_representable_below(), Some(1));
+    assert_eq!((-0.0f64).count_representable_below(), Some(1));
+    assert_eq!(f64::from_bits(10).count_representable_below(), Some(11));
+    let below_two = 2.0f64.count_representable_below().unwrap();
+    assert_eq!(below_two - 1.0f64.count_representable_below().unwrap(), 1 << 52);
+    assert_eq!(f64::MAX.count_representable_below(), Some(0x7ff0_0000_0000_0000));
+    assert_eq!(f64::INFINITY.count_representable_below(), None);
+    assert_eq!(f64::NAN.count_representable_below(), None);
+    assert_eq!((-f64::from_bits(1)).count_representable_below(), None);
+}
+
+#[test]
+fn test_flat_u128() {
+    let xs = [
+        1.0f64, -0.0, 0.0, 0.1, -f64::MAX, f64::from_bits(1), f64::MIN_POSITIVE.next_down(),
+        f64::NEG_INFINITY, f64::NAN, f64::from_bits(0x7ff0_0000_0000_0001),
+    ];
+    for x in xs {
+        assert_eq!(f64::from_flat_u128(x.to_flat_u128()).to_bits(), x.to_bits(), "{:e}", x);
+    }
+    for x in [1.5f32, -f32::from_bits(3), f32::MAX, f32::INFINITY, -f32::NAN] {
+        let flat = x.to_flat_u128();
+        assert_eq!(flat, (x as f64).to_flat_u128());
+        assert_eq!(f32::from_flat_u128(flat).to_bits(), x.to_bits());
+    }
+
+    // The same bit pattern is subnormal as f32 but normal as f64.
+    let bits = 0x0040_0000u32;
+    let narrow = f32::from_bits(bits).to_flat_u128();
+    let wide = f64::from_bits(bits as u64).to_flat_u128();
+    assert_ne!(narrow, wide);
+    assert_eq!(narrow, f32::powi(2.0, -127).to_flat_u128());
+    assert_eq!(narrow >> 103 & 1, 1);
+    let nan = f64::from_bits(0x7ff0_0000_0000_0001);
+    assert!(f32::from_flat_u128(nan.to_flat_u128()).is_nan());
+}
+
+#[test]
+fn test_min_max_f64() {
+    let values = [0.0f64, 3.0, f64::NAN, -0.0, -2.5, 1.0e-310, -f64::NAN, 7.0];
+    let (lo, hi) = min_max(&values).unwrap();
+    assert_eq!((lo, hi), (-2.5, 7.0));
+    let (lo, hi) = min_max(&[0.0f64, -0.0]).unwrap();
+    assert_eq!((lo.to_bits(), hi.to_bits()), ((-0.0f64).to_bits(), 0.0f64.to_bits()));
+    assert_eq!(min_max(&[f64::NEG_INFINITY, f64::NAN]), Some((f64::NEG_INFINITY, f64::NEG_INFINITY)));
+    assert_eq!(min_max::<f64>(&[]), None);
+}
+
+#[test]
+fn test_two_diff() {
+    // Knuth's TwoSum on the negated operand, as an oracle.
+    fn oracle(a: f64, b: f64) -> (f64, f64) {
+        let d = a - b;
+        let bv = a - d;
+        let av = d - bv;
+        (d, (a - av) - (b + bv))
+    }
+
+    let near = 1.0 - 1.0e-16;
+    let (d, e) = 1.0f64.two_diff(near);
+    assert_eq!((d, e), (f64::EPSILON / 2.0, 0.0));
+    assert_eq!(d + e, 1.0 - near);
+    let cases = [(1.0f64, 1.0e-16), (0.1, 0.3), (1.0e16, -1.0), (-2.5e-300, 3.0e-310), (3.0, 3.0)];
+    for (a, b) in cases {
+        assert_eq!(a.two_diff(b), oracle(a, b), "{:e} - {:e}", a, b);
+    }
+}
+
+#[test]
+fn test_category_example_f64() {
+    use std::num::FpCategory;
+
+    let cats = [FpCategory::Normal, FpCategory::Subnormal, FpCategory::Zero, FpCategory::Infinite, FpCategory::Nan];
+    for cat in cats {
+        assert_eq!(f64::category_example(cat).classify(), cat);
+        assert_eq!(f32::category_example(cat).classify(), cat);
+    }
+    assert_eq!(f64::category_example(FpCategory::Normal), 1.0);
+    assert_eq!(f64::category_example(FpCategory::Zero).to_bits(), 0);
+    assert_eq!(f64::category_example(FpCategory::Nan).to_bits(), f64::NAN.to_bits());
+}
+
+#[test]
+fn test_agreeing_bits_f64() {
+    assert_eq!(1.0f64.agreeing_bits(1.0 + f64::EPSILON), 52);
+    assert_eq!(0.1f64.agreeing_bits(0.1), 53);
+    assert_eq!(0.0f64.agreeing_bits(-0.0), 53);
+    assert_eq!(f64::INFINITY.agreeing_bits(f64::INFINITY), 53);
+    assert_eq!(f64::INFINITY.agreeing_bits(f64::NEG_INFINITY), 0);
+    assert_eq!(f64::INFINITY.agreeing_bits(f64::MAX), 0);
+    assert_eq!(f64::NAN.agreeing_bits(f64::NAN), 0);
+    assert_eq!(1.0f64.agreeing_bits(0.0), 0);
+    assert_eq!(1.0f64.agreeing_bits(1.0f64.next_down()), 0);
+    assert_eq!(3.0f64.agreeing_bits(3.0 + 3.0 * f64::powi(2.0, -20)), 20);
+    assert_eq!(f64::from_bits(6).agreeing_bits(f64::from_bits(7)), 2);
+    assert_eq!(1.0f64.agreeing_bits(1.0e-300), 0);
+}
+
+#[test]
+fn test_round_as_f64() {
+    use RoundingMode::*;
+
+    // 1.3 is 1.0100110011... in binary; at 4 bits it lies between
+    // 1.25 and 1.375, nearer 1.25. 1.4375 is the tie 1.0111.
+    let cases = [
+        (1.3f64, [1.25, 1.25, 1.375, 1.25]),
+        (-1.3, [-1.25, -1.25, -1.25, -1.375]),
+        (1.4375, [1.5, 1.375, 1.5, 1.375]),
+        (-1.3125, [-1.25, -1.25, -1.25, -1.375]),
+        (1.25, [1.25, 1.25, 1.25, 1.25]),
+    ];
+    for (x, expected) in cases {
+        for (mode, y) in [NearestEven, TowardZero, TowardPositive, TowardNegative].into_iter().zip(expected) {
+            assert_eq!(x.round_as(mode, 4), y, "{} {:?}", x, mode);
+        }
+    }
+    assert_eq!(f64::MAX.round_as(TowardPositive, 8), f64::INFINITY);
+    assert_eq!(f64::MAX.round_as(TowardZero, 8), f64::from_float_parts(0xff << 45, f64::EXP_MAX, 1));
+    assert_eq!((-f64::from_bits(7)).round_as(TowardPositive, 2), -f64::from_bits(6));
+}
+
+#[test]
+fn test_to_unit_mantissa_f64() {
+    assert_eq!(6.0f64.to_unit_mantissa(), 1.5);
+    assert_eq!((-6.0f64).to_unit_mantissa(), -1.5);
+    assert_eq!(f64::MAX.to_unit_mantissa(), 2.0 - f64::EPSILON);
+    assert_eq!(f64::from_bits(5).to_unit_mantissa(), 1.25);
+    assert_eq!((-0.0f64).to_unit_mantissa().to_bits(), (-0.0f64).to_bits());
+    assert_eq!(f64::NEG_INFINITY.to_unit_mantissa(), f64::NEG_INFINITY);
+    for x in [0.1f64, 3.0e-308, -7.0e200] {
+        let m = x.to_unit_mantissa();
+        assert!((1.0..2.0).contains(&m.abs()));
+        assert_eq!(m * x.abs().exponent_as_float(), x);
+    }
+}
+
+#[test]
+fn test_same_value_as_f64() {
+    for x in [1.0f32, -0.1, f32::MAX, f32::from_bits(1), -f32::MIN_POSITIVE, 3.0e-42] {
+        assert!(same_value_as_f64(x, x as f64), "{:e}", x);
+        assert!(!same_value_as_f64(x, (x as f64).next_up()), "{:e}", x);
+        assert!(!same_value_as_f64(x, -(x as f64)), "{:e}", x);
+    }
+    assert!(same_value_as_f64(-0.0, 0.0));
+    assert!(same_value_as_f64(f32::INFINITY, f64::INFINITY));
+    assert!(!same_value_as_f64(f32::INFINITY, f64::NEG_INFINITY));
+    assert!(!same_value_as_f64(f32::MAX, f64::INFINITY));
+    assert!(!same_value_as_f64(f32::NAN, f64::NAN));
+    assert!(!same_value_as_f64(1.0, f64::NAN));
+}
+
+#[test]
+fn test_suggest_fixed_point_format_f64() {
+    let values = [0.25f64, 1.5, -3.75, 100.0, 0.0];
+    assert_eq!(suggest_fixed_point_format(&values), Some((7, 2)));
+    assert_eq!(suggest_fixed_point_format(&[127.0f64, 0.5]), Some((7, 1)));
+    assert_eq!(suggest_fixed_point_format(&[128.0f64]), Some((8, 0)));
+    assert_eq!(suggest_fixed_point_format(&[0.375f64]), Some((0, 3)));
+    assert_eq!(suggest_fixed_point_format(&[1.0f64, f64::NAN]), None);
+    assert_eq!(suggest_fixed_point_format(&[f64::INFINITY]), None);
+    assert_eq!(suggest_fixed_point_format::<f64>(&[]), None);
+    let (int_bits, frac_bits) = suggest_fixed_point_format(&values).unwrap();
+    for x in values {
+        let fixed = x.to_fixed_point(frac_bits).unwrap();
+        assert!(fixed.unsigned_abs() < 1 << (int_bits + frac_bits));
+        assert_eq!(f64::from_fixed_point(fixed, frac_bits), x);
+    }
+}
+
+#[test]
+fn test_parts_float_f64() {
+    let xs = [
+        0.1f64, 0.2, -0.3, 1.0, 1.0e16, -1.0e-16, 3.0e-310, -2.5e-320, 1.0e300, 7.0, -0.0, 0.0,
+        f64::MAX, f64::INFINITY, f64::NAN,
+    ];
+    for a in xs {
+        for b in xs {
+            let (pa, pb) = (PartsFloat(a), PartsFloat(b));
+            for (got, want) in [((pa + pb).0, a + b), ((pa - pb).0, a - b), ((pa * pb).0, a * b)] {
+                if want.is_nan() {
+                    assert!(got.is_nan(), "{:e} {:e}", a, b);
+                } else {
+                    assert_eq!(got.to_bits(), want.to_bits(), "{:e} {:e}", a, b);
+                }
+            }
+        }
+    }
+    assert_eq!((-PartsFloat(0.0f64)).0.to_bits(), (-0.0f64).to_bits());
+}
+
+#[test]
+fn test_difference_exponent_f64() {
+    assert_eq!(1.0f64.difference_exponent(1.25), Some(-2));
+    assert_eq!(12.0f64.difference_exponent(10.0), Some(1));
+    assert_eq!(1.0f64.difference_exponent(1.0 + f64::EPSILON), Some(-52));
+    assert_eq!(1.0f64.difference_exponent(1.0e-300), Some(-1));
+    assert_eq!(1.0e300f64.difference_exponent(1.0), Some(996));
+    assert_eq!(f64::MAX.difference_exponent(-f64::MAX), Some(1024));
+    assert_eq!(f64::from_bits(3).difference_exponent(f64::from_bits(2)), Some(-1074));
+    assert_eq!(0.0f64.difference_exponent(-0.0), None);
+    assert_eq!(0.0f64.difference_exponent(f64::INFINITY), None);
+    assert_eq!(f64::NAN.difference_exponent(1.0), None);
+    for (a, b) in [(0.1f64, 0.3), (5.0, 4.9), (-2.0e-300, 1.0e-300)] {
+        assert_eq!(a.difference_exponent(b), (a - b).binade(), "{:e} {:e}", a, b);
+    }
+}
+
+#[test]
+fn test_quantize_to_pow2_f64() {
+    assert_eq!(1.3f64.quantize_to_pow2(-2), 1.25);
+    assert_eq!((-1.3f64).quantize_to_pow2(-2), -1.25);
+    assert_eq!(0.125f64.quantize_to_pow2(-2), 0.0);
+    assert_eq!(0.375f64.quantize_to_pow2(-2), 0.5);
+    assert_eq!((-0.1f64).quantize_to_pow2(0).to_bits(), (-0.0f64).to_bits());
+    assert_eq!(1.75f64.quantize_to_pow2(-2), 1.75);
+    assert_eq!(f64::MAX.quantize_to_pow2(1000), f64::INFINITY);
+    assert_eq!(1.0f64.quantize_to_pow2(i16::MAX), 0.0);
+    assert_eq!(f64::from_bits(3).quantize_to_pow2(-1073), f64::from_bits(4));
+    assert!(f64::NAN.quantize_to_pow2(0).is_nan());
+    for x in [2.5f64, -7.5, 123.456, 0.49] {
+        assert_eq!(x.quantize_to_pow2(0), x.round_ties_even());
+    }
+}
+
+#[test]
+fn test_is_dyadic_short_f64() {
+    assert!(0.5f64.is_dyadic_short(4));
+    assert!(0.5f64.is_dyadic_short(1));
+    assert!(0.75f64.is_dyadic_short(4));
+    assert!(!0.1f64.is_dyadic_short(4));
+    // 0.1 rounds to an even significand, 52 bits once trimmed.
+    assert!(!0.1f64.is_dyadic_short(51));
+    assert!(0.1f64.is_dyadic_short(52));
+    assert!((-13.0f64 * f64::powi(2.0, -1070)).is_dyadic_short(4));
+    assert!(f64::from_bits(1).is_dyadic_short(1));
+    assert!(0.0f64.is_dyadic_short(0));
+    assert!(!f64::INFINITY.is_dyadic_short(53));
+    assert!(!f64::NAN.is_dyadic_short(53));
+}
+
+#[test]
+fn test_dekker_split_f64() {
+    let xs = [0.1f64, -std::f64::consts::PI, f64::MAX, 1.0e-310, -f64::from_bits(mask!(52)), 1.0 / 3.0];
+    for x in xs {
+        let (high, low) = x.dekker_split();
+        assert_eq!(high + low, x, "{:e}", x);
+        let (hs, _, _) = high.to_float_parts();
+        assert!(64 - hs.leading_zeros() - hs.trailing_zeros() <= 27, "{:e}", x);
+        assert!(low.abs() < high.abs() * f64::powi(2.0, -26), "{:e}", x);
+    }
+    let (high, low) = (-0.0f64).dekker_split();
+    assert_eq!((high.to_bits(), low.to_bits()), ((-0.0f64).to_bits(), (-0.0f64).to_bits()));
+    assert_eq!(f64::INFINITY.dekker_split(), (f64::INFINITY, 0.0));
+}
+
+#[test]
+fn test_cancellation_bits_f64() {
+    assert_eq!(1.0f64.cancellation_bits(1.0 + f64::EPSILON), 52);
+    assert_eq!(1.0f64.cancellation_bits(1.0f64.next_down()), 53);
+    assert_eq!((-2.5f64).cancellation_bits(-2.5), 53);
+    assert_eq!(0.1f64.cancellation_bits(0.1000001), 20);
+    assert_eq!(1.0e300f64.cancellation_bits(1.0e-300), 0);
+    assert_eq!(3.0f64.cancellation_bits(1.0), 0);
+    assert_eq!(1.0f64.cancellation_bits(-1.0), 0);
+    assert_eq!(0.0f64.cancellation_bits(0.0), 0);
+    assert_eq!(f64::INFINITY.cancellation_bits(f64::INFINITY), 0);
+    assert_eq!(f64::NAN.cancellation_bits(1.0), 0);
+}
+
+#[test]
+fn test_binade_weights_f64() {
+    let weights = f64::binade_weights();
+    assert_eq!(weights.len(), 2047);
+    assert_eq!(weights[0], (f64::EXP_MIN, (1 << 52) - 1));
+    assert!(weights[1..].iter().all(|&(_, n)| n == 1 << 52));
+    assert!(weights.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(weights[1].0, f64::MIN_POSITIVE.binade().unwrap());
+    assert_eq!(weights[2046].0, f64::MAX.binade().unwrap());
+    let total: u64 = weights.iter().map(|&(_, n)| n).sum();
+    assert_eq!(total + 1, f64::MAX.count_representable_below().unwrap());
+}
+
+#[test]
+fn test_nearest_in_format() {
+    let single = FloatFormat { num_sig_bits: 24, num_exp_bits: 8, has_infinity: true };
+    let xs = [
+        0.1f64, -1.0 / 3.0, 1.0e-40, -1.0e-45, 7.0e-46, 1.0e-50, 3.4028235677973366e38, 3.5e38,
+        1.0 + f64::powi(2.0, -24), 1.0 + 3.0 * f64::powi(2.0, -24), f64::INFINITY, 0.0, -0.0,
+    ];
+    for x in xs {
+        let (sigbits, exp, sign) = (x as f32).to_float_parts();
+        assert_eq!(nearest_in_format(x, single), (sigbits as u128, exp as i32, sign), "{:e}", x);
+    }
+    let (sigbits, exp, _) = nearest_in_format(f64::NAN, single);
+    assert_eq!(f32::from_float_parts(sigbits as u32, exp as i16, 1).to_bits(), f32::NAN.to_bits());
+
+    let fmt = FloatFormat { num_sig_bits: 5, num_exp_bits: 5, has_infinity: true };
+    assert_eq!(nearest_in_format(0.1, fmt), (26, -8, 1));
+    // Ties to even: 1.00001b rounds down, 1.00011b rounds up.
+    assert_eq!(nearest_in_format(1.03125, fmt), (16, -4, 1));
+    assert_eq!(nearest_in_format(1.09375, fmt), (18, -4, 1));
+
+    let e4m3 = FloatFormat::FP8_E4M3;
+    assert_eq!(nearest_in_format(448.0, e4m3), (14, 5, 1));
+    assert_eq!(nearest_in_format(-1.0e10, e4m3), (14, 5, -1));
+    assert_eq!(nearest_in_format(f64::INFINITY, e4m3), (14, 5, 1));
+    assert_eq!(nearest_in_format(f64::NAN, e4m3), (7, 5, 1));
+    // The smallest E4M3 subnormal is 2^-9.
+    assert_eq!(nearest_in_format(f64::powi(2.0, -9), e4m3), (2, -10, 1));
+    assert_eq!(nearest_in_format(f64::powi(2.0, -11), e4m3), (0, -10, 1));
+    let e5m2 = FloatFormat::FP8_E5M2;
+    assert_eq!(nearest_in_format(57344.0, e5m2), (7, 13, 1));
+    // Halfway to the next binade rounds up to infinity.
+    assert_eq!(nearest_in_format(61440.0, e5m2), (0, 14, 1));
+    assert_eq!(nearest_in_format(61439.0, e5m2), (7, 13, 1));
+    assert_eq!(nearest_in_format(-1.0e6, e5m2), (0, 14, -1));
+}
+
+#[test]
+fn test_to_exact_decimal_string_f64() {
+    assert_eq!(0.1f64.to_exact_decimal_string(), "0.1000000000000000055511151231257827021181583404541015625");
+    assert_eq!(1.0f64.to_exact_decimal_string(), "1");
+    assert_eq!((-0.0f64).to_exact_decimal_string(), "-0");
+    assert_eq!(0.75f64.to_exact_decimal_string(), "0.75");
+    assert_eq!(f64::NEG_INFINITY.to_exact_decimal_string(), "-inf");
+    assert_eq!(f64::NAN.to_exact_decimal_string(), "NaN");
+    assert_eq!(f64::powi(2.0, 70).to_exact_decimal_string(), "1180591620717411303424");
+    let max = f64::MAX.to_exact_decimal_string();
+    assert_eq!(max.len(), 309);
+    assert!(max.starts_with("17976931348623157") && max.ends_with("858368"));
+    let tiny = f64::from_bits(1).to_exact_decimal_string();
+    assert!(tiny.starts_with("0.000") && tiny.ends_with("5625"));
+    assert_eq!(tiny.len(), 2 + 1074);
+    assert_eq!(tiny.parse::<f64>().unwrap(), f64::from_bits(1));
+    for x in [0.3f64, -123.456, 6.02e23, 1.0e-300] {
+        assert_eq!(x.to_exact_decimal_string().parse::<f64>().unwrap(), x);
+    }
+}
+
+#[test]
+fn test_survives_decimal_roundtrip() {
+    let sum = 0.1f64 + 0.2;
+    assert!(!sum.survives_decimal_roundtrip(16));
+    assert!(sum.survives_decimal_roundtrip(17));
+    assert!(!1014618432.0f32.survives_decimal_roundtrip(8));
+    assert!(1014618432.0f32.survives_decimal_roundtrip(9));
+    // A crude pseudorandom sweep of bit patterns.
+    let mut bits = 0x1234_5678_9abc_def0u64;
+    for _ in 0..2000 {
+        bits = bits.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let (x, y) = (f64::from_bits(bits), f32::from_bits((bits >> 32) as u32));
+        if x.is_finite() {
+            assert!(x.survives_decimal_roundtrip(17), "{:e}", x);
+        }
+        if y.is_finite() {
+            assert!(y.survives_decimal_roundtrip(9), "{:e}", y);
+        }
+    }
+    assert!(f64::NEG_INFINITY.survives_decimal_roundtrip(1));
+    assert!(!1.0f64.survives_decimal_roundtrip(0));
+    assert!(!0.0f32.survives_decimal_roundtrip(0));
+    assert!(1.0e-310f64.survives_decimal_roundtrip(u32::MAX));
+    assert!(f32::MAX.survives_decimal_roundtrip(u32::MAX));
+    assert!(f32::INFINITY.survives_decimal_roundtrip(u32::MAX));
+    assert!(!f64::from_bits(0x7ff0_0000_0000_0001).survives_decimal_roundtrip(u32::MAX));
+    assert!(!f64::from_bits(0x7ff0_0000_0000_0001).survives_decimal_roundtrip(17));
+}
+
+#[test]
+fn test_representable_range() {
+    assert_eq!(f64::representable_range(), (-f64::MAX, f64::MAX));
+    assert_eq!(f32::representable_range(), (-f32::MAX, f32::MAX));
+    let (lo, hi) = f64::representable_range();
+    assert_eq!(lo.next_down(), f64::NEG_INFINITY);
+    assert_eq!(hi.next_up(), f64::INFINITY);
+}
+
+#[test]
+fn test_alignment_shift_f64() {
+    assert_eq!(4.0f64.alignment_shift(1.0), Some(2));
+    assert_eq!(1.0f64.alignment_shift(4.0), Some(-2));
+    assert_eq!((-1.5f64).alignment_shift(1.0), Some(0));
+    assert_eq!(f64::MAX.alignment_shift(f64::from_bits(1)), Some(f64::EXP_MAX - f64::EXP_MIN));
+    assert_eq!(f64::MIN_POSITIVE.alignment_shift(f64::from_bits(1)), Some(1));
+    assert_eq!(f64::INFINITY.alignment_shift(1.0), None);
+
+    // Shifting by the alignment lines the sigbits up for an exact sum.
+    let (a, b) = (12.0f64, 0.375);
+    let shift = a.alignment_shift(b).unwrap() as u32;
+    let ((sa, _, _), (sb, eb, _)) = (a.to_float_parts(), b.to_float_parts());
+    assert_eq!(f64::from_normalized_significand(((sa << shift) + sb) as u128, eb as i32, 1), a + b);
+}
+
+#[test]
+fn test_with_trailing_zeros_f64() {
+    let ns = f64::NUM_SIG_BITS;
+    for x in [std::f64::consts::PI, -0.1, 1.0e300, f64::MAX, -f64::MIN_POSITIVE] {
+        let y = x.with_trailing_zeros(ns - 1);
+        assert_eq!(y, x.signum() * x.abs().exponent_as_float(), "{:e}", x);
+        assert_eq!(y.to_float_parts().0, 1 << (ns - 1));
+        assert_eq!(x.with_trailing_zeros(0), x);
+        assert_eq!(x.with_trailing_zeros(ns).to_bits(), (0.0f64).with_sign(x.signum() as i8).to_bits());
+    }
+    let z = 0.1f64.with_trailing_zeros(20);
+    assert!(z <= 0.1 && z.to_float_parts().0.trailing_zeros() >= 20);
+    // 0.1 has sigbits exponent -56, so z is a multiple of 2^-36.
+    assert_eq!(z.fractional_bit_count(), 36);
+    assert_eq!(f64::from_fixed_point(z.to_fixed_point(36).unwrap(), 36), z);
+    assert_eq!(f64::from_bits(0x7ff0_0000_0000_0001).with_trailing_zeros(10).to_bits(), 0x7ff0_0000_0000_0001);
+    assert_eq!(f64::NEG_INFINITY.with_trailing_zeros(5), f64::NEG_INFINITY);
 }